@@ -0,0 +1,337 @@
+use bevy::prelude::*;
+
+use crate::chef::ChefHitEvent;
+use crate::chef::skins::unlock_achievement_cosmetics;
+use crate::components::Clock;
+use crate::fruits::boost::BoostUsedEvent;
+use crate::fruits::fruit::SliceEvent;
+use crate::fruits::penalty::WaveEvent;
+use crate::global::AppState;
+use crate::level::LevelUpdate;
+use crate::sound::{SoundEvent, SoundType};
+use crate::utils::save::SaveData;
+use crate::utils::ui::text;
+
+
+pub const ACHIEVEMENTS_KEY: &str = "achievements";
+
+const TOAST_DURATION: f32 = 3.;
+
+const FROM_MENU_ENTER: OnTransition<AppState> = OnTransition::<AppState> {
+    from: AppState::MainMenu,
+    to: AppState::InGame
+};
+
+const FROM_FINISH_ENTER: OnTransition<AppState> = OnTransition::<AppState> {
+    from: AppState::Finish,
+    to: AppState::InGame
+};
+
+
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(RunProgress::default())
+            .add_systems(FROM_MENU_ENTER, reset_progress)
+            .add_systems(FROM_FINISH_ENTER, reset_progress)
+            .add_systems(Update, track.run_if(in_state(AppState::InGame)))
+            .add_systems(Update, (show_toast, hide_toast))
+            .add_event::<AchievementEvent>()
+        ;
+    }
+}
+
+
+/// What an achievement is measured by.
+/// Cumulative stats are summed across runs, the rest only count their best value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stat {
+    SwingCombo,
+    Level,
+    FlawlessLevel,
+    BoostsUsed,
+    FruitsSliced,
+    Swings
+}
+
+impl Stat {
+    const fn cumulative_key(self) -> Option<&'static str> {
+        match self {
+            Self::BoostsUsed => Some("progress.boosts"),
+            Self::FruitsSliced => Some("progress.sliced"),
+            Self::Swings => Some("progress.swings"),
+            _ => None
+        }
+    }
+}
+
+
+pub struct Achievement {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub stat: Stat,
+    pub target: u32
+}
+
+
+pub const ACHIEVEMENTS: [Achievement; 7] = [
+    Achievement {
+        id: "triple",
+        name: "Triple cut",
+        description: "Slice 3 fruits in one swing",
+        stat: Stat::SwingCombo,
+        target: 3
+    },
+    Achievement {
+        id: "level10",
+        name: "Warming up",
+        description: "Reach level 10",
+        stat: Stat::Level,
+        target: 10
+    },
+    Achievement {
+        id: "level20",
+        name: "Master chef",
+        description: "Reach level 20",
+        stat: Stat::Level,
+        target: 20
+    },
+    Achievement {
+        id: "flawless",
+        name: "Flawless",
+        description: "Reach level 10 without losing a life",
+        stat: Stat::FlawlessLevel,
+        target: 10
+    },
+    Achievement {
+        id: "boosts50",
+        name: "Boost addict",
+        description: "Use 50 boosts",
+        stat: Stat::BoostsUsed,
+        target: 50
+    },
+    Achievement {
+        id: "sliced1000",
+        name: "Fruit salad",
+        description: "Slice 1000 fruits",
+        stat: Stat::FruitsSliced,
+        target: 1000
+    },
+    Achievement {
+        id: "swings5000",
+        name: "Busy blade",
+        description: "Swing the knife 5000 times",
+        stat: Stat::Swings,
+        target: 5000
+    },
+];
+
+
+impl Achievement {
+    pub fn is_unlocked(&self, save: &SaveData) -> bool {
+        save.contains(ACHIEVEMENTS_KEY, self.id)
+    }
+
+    /// Progress towards the target, only tracked for cumulative stats.
+    pub fn progress(&self, save: &SaveData) -> Option<u32> {
+        self.stat.cumulative_key().map(|key| save.get_u32(key).min(self.target))
+    }
+}
+
+
+/// Records a new value of `stat` and unlocks every achievement it satisfies.
+/// Returns the newly unlocked achievements.
+pub fn record(save: &mut SaveData, stat: Stat, mut value: u32) -> Vec<&'static Achievement> {
+    if let Some(key) = stat.cumulative_key() {
+        value += save.get_u32(key);
+        save.set(key, value);
+    }
+
+    ACHIEVEMENTS
+        .iter()
+        .filter(|achievement| achievement.stat == stat && value >= achievement.target)
+        .filter(|achievement| save.push(ACHIEVEMENTS_KEY, achievement.id))
+        .collect()
+}
+
+
+#[derive(Event)]
+pub struct AchievementEvent(pub &'static Achievement);
+
+
+#[derive(Resource, Default)]
+struct RunProgress {
+    lost_life: bool
+}
+
+#[derive(Component)]
+struct Toast;
+
+
+fn reset_progress(mut progress: ResMut<RunProgress>) {
+    progress.lost_life = false;
+}
+
+
+fn track(
+    mut save: ResMut<SaveData>,
+    mut progress: ResMut<RunProgress>,
+
+    mut swings: EventReader<ChefHitEvent>,
+    mut slices: EventReader<SliceEvent>,
+    mut waves: EventReader<WaveEvent>,
+    mut levels: EventReader<LevelUpdate>,
+    mut boosts: EventReader<BoostUsedEvent>,
+
+    mut unlocked: EventWriter<AchievementEvent>
+) {
+    if !waves.is_empty() {
+        progress.lost_life = true;
+        waves.clear();
+    }
+
+    let mut records = Vec::new();
+
+    if !swings.is_empty() {
+        records.push((Stat::Swings, swings.len() as u32));
+        swings.clear();
+    }
+
+    for event in &mut slices {
+        records.push((Stat::FruitsSliced, event.fruits.len() as u32));
+
+        if !event.boost {
+            records.push((Stat::SwingCombo, event.fruits.len() as u32));
+        }
+    }
+
+    for event in &mut levels {
+        records.push((Stat::Level, event.number));
+
+        if !progress.lost_life {
+            records.push((Stat::FlawlessLevel, event.number));
+        }
+    }
+
+    if !boosts.is_empty() {
+        records.push((Stat::BoostsUsed, boosts.len() as u32));
+        boosts.clear();
+    }
+
+    for (stat, value) in records {
+        let achievements = record(&mut save, stat, value);
+        if !achievements.is_empty() {
+            unlock_achievement_cosmetics(&mut save);
+        }
+
+        for achievement in achievements {
+            unlocked.send(AchievementEvent(achievement));
+        }
+    }
+}
+
+
+fn show_toast(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut events: EventReader<AchievementEvent>,
+    mut sound: EventWriter<SoundEvent>,
+    toasts: Query<(), With<Toast>>
+) {
+    let shown = toasts.iter().count();
+
+    for (count, AchievementEvent(achievement)) in (shown..).zip(&mut events) {
+        let message = format!("Achievement unlocked: {}", achievement.name);
+        let mut toast = text(&asset_server, &message, 0., 290. - 40. * count as f32, 32.);
+        toast.transform.translation.z = 20.;
+
+        commands.spawn((toast, Toast, Clock::seconds_once(TOAST_DURATION)));
+        sound.send(SoundEvent::sound(SoundType::ACHIEVEMENT));
+    }
+}
+
+
+fn hide_toast(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(&mut Text, &mut Clock, Entity), With<Toast>>
+) {
+    for (mut text, mut clock, entity) in &mut query {
+        clock.tick(time.delta());
+
+        // fade out during the last half of the toast lifetime
+        let alpha = ((1. - clock.percent()) * 2.).min(1.);
+        text.sections[0].style.color.set_a(alpha);
+
+        if clock.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::chef::ChefHitEvent;
+    use crate::chef::skins::{is_unlocked, TRAILS};
+    use crate::fruits::boost::BoostUsedEvent;
+    use crate::fruits::fruit::{FruitType, SliceEvent};
+    use crate::fruits::penalty::WaveEvent;
+    use crate::level::LevelUpdate;
+    use crate::utils::save::SaveData;
+    use super::{record, track, AchievementEvent, RunProgress, Stat, ACHIEVEMENTS};
+
+    #[test]
+    fn test_it_unlocks_peak_achievements_once() {
+        let mut save = SaveData::default();
+
+        assert!(record(&mut save, Stat::SwingCombo, 2).is_empty());
+
+        let unlocked = record(&mut save, Stat::SwingCombo, 3);
+        assert_eq!(unlocked.len(), 1);
+        assert_eq!(unlocked[0].id, "triple");
+
+        assert!(record(&mut save, Stat::SwingCombo, 3).is_empty());
+    }
+
+    #[test]
+    fn test_it_accumulates_progress() {
+        let mut save = SaveData::default();
+        let boosts = ACHIEVEMENTS.iter().find(|a| a.id == "boosts50").unwrap();
+
+        assert!(record(&mut save, Stat::BoostsUsed, 30).is_empty());
+        assert_eq!(boosts.progress(&save), Some(30));
+
+        let unlocked = record(&mut save, Stat::BoostsUsed, 20);
+        assert_eq!(unlocked.len(), 1);
+        assert!(boosts.is_unlocked(&save));
+    }
+
+    #[test]
+    fn test_it_unlocks_cosmetics_as_achievements_are_recorded() {
+        let mut app = App::new();
+        app
+            .insert_resource(SaveData::default())
+            .init_resource::<RunProgress>()
+            .add_event::<ChefHitEvent>()
+            .add_event::<SliceEvent>()
+            .add_event::<WaveEvent>()
+            .add_event::<LevelUpdate>()
+            .add_event::<BoostUsedEvent>()
+            .add_event::<AchievementEvent>()
+            .add_systems(Update, track);
+
+        app.world.send_event(SliceEvent { fruits: vec![FruitType::APPLE; 3], boost: false, player: 0 });
+        app.update();
+
+        // no toast has been shown, the trail is unlocked all the same
+        let juice = &TRAILS[3];
+        assert!(is_unlocked(app.world.resource::<SaveData>(), juice.id, juice.unlock));
+    }
+}
@@ -0,0 +1,279 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::global::AppState;
+
+
+pub struct SpriteAnimationPlugin;
+
+impl Plugin for SpriteAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, animate.run_if(not(in_state(AppState::Paused))))
+            .add_event::<AnimationFinished>()
+        ;
+    }
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlayMode {
+    Loop,
+    Once,
+    /// Plays forward and back again, forever.
+    #[allow(dead_code)]
+    PingPong
+}
+
+
+/// What happens when a `PlayMode::Once` clip shows its last frame for its full duration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OnFinished {
+    /// Keep showing the last frame.
+    Hold,
+    /// Despawn the entity with the sprite.
+    #[allow(dead_code)]
+    Despawn,
+    /// Remove the `SpriteAnimation` component, the sprite keeps its last frame.
+    Remove,
+    /// Send an `AnimationFinished` event.
+    Event,
+    /// Switch to another clip of the same animation.
+    Play(&'static str)
+}
+
+
+/// Range of frames in a sprite sheet, both ends included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Clip {
+    pub name: &'static str,
+    pub first: usize,
+    pub last: usize,
+    /// Frame durations in milliseconds, the last one is used for every remaining frame.
+    pub durations: &'static [u64],
+    pub mode: PlayMode,
+    pub on_finished: OnFinished
+}
+
+impl Clip {
+    /// Clip that plays once at a constant frame rate and keeps its last frame.
+    pub const fn new(name: &'static str, first: usize, last: usize, durations: &'static [u64]) -> Self {
+        Self { name, first, last, durations, mode: PlayMode::Once, on_finished: OnFinished::Hold }
+    }
+
+    pub const fn mode(mut self, mode: PlayMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub const fn then(mut self, on_finished: OnFinished) -> Self {
+        self.on_finished = on_finished;
+        self
+    }
+
+    const fn len(&self) -> usize {
+        self.last - self.first + 1
+    }
+
+    fn duration(&self, frame: usize) -> Duration {
+        let millis = self.durations.get(frame).or_else(|| self.durations.last()).copied().unwrap_or(100);
+        // zero length frames would never let the loop in `tick` end
+        Duration::from_millis(millis.max(1))
+    }
+}
+
+
+/// Sent by clips that end with `OnFinished::Event`.
+#[derive(Event, Debug)]
+pub struct AnimationFinished {
+    pub entity: Entity
+}
+
+
+/// Plays clips of a `TextureAtlasSprite`, the first clip starts right away.
+#[derive(Component, Debug)]
+pub struct SpriteAnimation {
+    clips: Vec<Clip>,
+    current: usize,
+    frame: usize,
+    forward: bool,
+    elapsed: Duration,
+    finished: bool
+}
+
+impl SpriteAnimation {
+    pub fn new(clips: impl Into<Vec<Clip>>) -> Self {
+        let clips = clips.into();
+        assert!(!clips.is_empty(), "animation without clips");
+
+        Self { clips, current: 0, frame: 0, forward: true, elapsed: Duration::ZERO, finished: false }
+    }
+
+    pub fn clip(&self) -> &Clip {
+        &self.clips[self.current]
+    }
+
+    /// Sprite sheet index of the frame on screen.
+    pub fn index(&self) -> usize {
+        self.clip().first + self.frame
+    }
+
+    /// Starts the named clip from its first frame, unknown names are ignored.
+    pub fn play(&mut self, name: &str) {
+        if let Some(current) = self.clips.iter().position(|clip| clip.name == name) {
+            self.current = current;
+            self.frame = 0;
+            self.forward = true;
+            self.elapsed = Duration::ZERO;
+            self.finished = false;
+        }
+    }
+
+    /// Advances the animation, returns `true` when the clip has just finished.
+    pub fn tick(&mut self, delta: Duration) -> bool {
+        if self.finished {
+            return false;
+        }
+
+        self.elapsed += delta;
+
+        loop {
+            let duration = self.clip().duration(self.frame);
+
+            if self.elapsed < duration {
+                return false;
+            }
+
+            self.elapsed -= duration;
+
+            if !self.step() {
+                self.finished = true;
+                self.elapsed = Duration::ZERO;
+                return true;
+            }
+        }
+    }
+
+    /// Moves to the next frame, returns `false` at the end of a `Once` clip.
+    fn step(&mut self) -> bool {
+        let last = self.clip().len() - 1;
+
+        match self.clip().mode {
+            PlayMode::Once => {
+                if self.frame == last {
+                    return false;
+                }
+                self.frame += 1;
+            }
+            PlayMode::Loop => {
+                self.frame = if self.frame == last { 0 } else { self.frame + 1 };
+            }
+            PlayMode::PingPong => {
+                if last == 0 {
+                    return true;
+                }
+                if self.frame == last {
+                    self.forward = false;
+                } else if self.frame == 0 {
+                    self.forward = true;
+                }
+                self.frame = if self.forward { self.frame + 1 } else { self.frame - 1 };
+            }
+        }
+
+        true
+    }
+}
+
+
+fn animate(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut finished: EventWriter<AnimationFinished>,
+    mut query: Query<(&mut SpriteAnimation, &mut TextureAtlasSprite, Entity)>
+) {
+    for (mut animation, mut sprite, entity) in &mut query {
+        if animation.tick(time.delta()) {
+            match animation.clip().on_finished {
+                OnFinished::Hold => {}
+                OnFinished::Despawn => commands.entity(entity).despawn(),
+                OnFinished::Remove => {
+                    commands.entity(entity).remove::<SpriteAnimation>();
+                }
+                OnFinished::Event => finished.send(AnimationFinished { entity }),
+                OnFinished::Play(name) => animation.play(name)
+            }
+        }
+
+        let index = animation.index();
+        if sprite.index != index {
+            sprite.index = index;
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Clip, OnFinished, PlayMode, SpriteAnimation};
+
+    const MS: Duration = Duration::from_millis(1);
+
+    fn frames(animation: &mut SpriteAnimation, steps: usize, step: Duration) -> Vec<usize> {
+        (0..steps).map(|_| { animation.tick(step); animation.index() }).collect()
+    }
+
+    #[test]
+    fn test_it_plays_once_and_finishes() {
+        let mut animation = SpriteAnimation::new([Clip::new("cut", 2, 4, &[10])]);
+        assert_eq!(animation.index(), 2);
+
+        assert_eq!(frames(&mut animation, 2, MS * 10), vec![3, 4]);
+        assert!(!animation.finished);
+
+        assert!(animation.tick(MS * 10));
+        assert!(animation.finished);
+        assert_eq!(animation.index(), 4);
+        assert!(!animation.tick(MS * 10));
+    }
+
+    #[test]
+    fn test_it_loops_and_ping_pongs() {
+        let mut looped = SpriteAnimation::new([Clip::new("idle", 0, 2, &[10]).mode(PlayMode::Loop)]);
+        assert_eq!(frames(&mut looped, 4, MS * 10), vec![1, 2, 0, 1]);
+
+        let mut ping_pong = SpriteAnimation::new([Clip::new("idle", 0, 2, &[10]).mode(PlayMode::PingPong)]);
+        assert_eq!(frames(&mut ping_pong, 6, MS * 10), vec![1, 2, 1, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_it_uses_per_frame_durations() {
+        let mut animation = SpriteAnimation::new([Clip::new("hit", 0, 3, &[10, 30])]);
+
+        // the second duration repeats for the remaining frames
+        assert_eq!(frames(&mut animation, 5, MS * 10), vec![1, 1, 1, 2, 2]);
+        // long frames can skip several frames at once
+        animation.tick(MS * 100);
+        assert!(animation.finished);
+    }
+
+    #[test]
+    fn test_it_switches_clips() {
+        let clips = [
+            Clip::new("idle", 0, 1, &[10]).mode(PlayMode::Loop),
+            Clip::new("slice", 5, 6, &[10]).then(OnFinished::Play("idle"))
+        ];
+        let mut animation = SpriteAnimation::new(clips);
+
+        animation.play("slice");
+        assert_eq!(animation.index(), 5);
+        assert_eq!(animation.clip().on_finished, OnFinished::Play("idle"));
+
+        animation.play("missing");
+        assert_eq!(animation.clip().name, "slice");
+    }
+}
@@ -0,0 +1,152 @@
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+
+use crate::chef::skins::SKINS;
+use crate::fruits::fruit::FruitType;
+use crate::music::{FINISH_TRACK, GAME_TRACK, MENU_TRACK};
+use crate::sound::{SoundKey, SoundType};
+
+
+/// Every image the game shows, relative to the assets folder.
+pub const IMAGES: [&str; 17] = [
+    "images/bg.png",
+    "images/menu.png",
+    "images/menu-record.png",
+    "images/title.png",
+    "images/keys.png",
+    "images/heart.png",
+    "images/heart-empty.png",
+    "images/splash.png",
+    "images/wave.png",
+    "images/boost_shot.png",
+    "images/fruits/apple-frames.png",
+    "images/fruits/strawberry.png",
+    "images/fruits/watermelon.png",
+    "images/fruits/orange.png",
+    "images/fruits/banana.png",
+    "images/fruits/pome.png",
+    "images/fruits/pineapple.png",
+];
+
+pub const FONTS: [&str; 1] = [
+    "fonts/mn-regular.otf",
+];
+
+
+/// All files loaded before the main menu: images, skins, fonts, sounds and music.
+pub fn manifest() -> Vec<&'static str> {
+    let skins = SKINS.iter().map(|skin| skin.image);
+
+    let sounds = SoundType::ALL
+        .into_iter()
+        .map(SoundKey::Effect)
+        .chain(FruitType::ALL.into_iter().map(SoundKey::Fruit))
+        .map(|key| key.spec().file);
+
+    let music = [MENU_TRACK, GAME_TRACK, FINISH_TRACK].into_iter().map(|track| track.file);
+
+    let mut files: Vec<&str> = IMAGES
+        .into_iter()
+        .chain(skins)
+        .chain(FONTS)
+        .chain(sounds)
+        .chain(music)
+        .collect();
+
+    files.sort_unstable();
+    files.dedup();
+    files
+}
+
+
+#[derive(Debug, PartialEq)]
+pub enum Progress {
+    /// Share of the manifest that finished loading, from 0 to 1.
+    Loading(f32),
+    Done,
+    Failed(Vec<&'static str>)
+}
+
+
+/// Handles to every manifest file, kept so the assets are never unloaded.
+#[derive(Resource, Default)]
+pub struct PreloadedAssets {
+    handles: Vec<(&'static str, HandleUntyped)>
+}
+
+impl PreloadedAssets {
+    pub fn load(asset_server: &AssetServer) -> Self {
+        let handles = manifest()
+            .into_iter()
+            .map(|file| (file, asset_server.load_untyped(file)))
+            .collect();
+
+        Self { handles }
+    }
+
+    pub fn progress(&self, asset_server: &AssetServer) -> Progress {
+        let states = self.handles
+            .iter()
+            .map(|(file, handle)| (*file, asset_server.get_load_state(handle)));
+
+        progress(states)
+    }
+}
+
+
+pub fn progress(states: impl Iterator<Item = (&'static str, LoadState)>) -> Progress {
+    let mut total = 0;
+    let mut loaded = 0;
+    let mut failed = Vec::new();
+
+    for (file, state) in states {
+        total += 1;
+
+        match state {
+            LoadState::Loaded => loaded += 1,
+            LoadState::Failed | LoadState::Unloaded => failed.push(file),
+            LoadState::NotLoaded | LoadState::Loading => {}
+        }
+    }
+
+    if !failed.is_empty() {
+        return Progress::Failed(failed);
+    }
+
+    if loaded == total {
+        Progress::Done
+    } else {
+        Progress::Loading(loaded as f32 / total as f32)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use bevy::asset::LoadState;
+    use super::{manifest, progress, Progress};
+
+    #[test]
+    fn test_it_finds_every_manifest_file() {
+        let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+
+        for file in manifest() {
+            assert!(assets.join(file).is_file(), "missing asset {file}");
+        }
+    }
+
+    #[test]
+    fn test_it_reports_progress() {
+        let states = [("a.png", LoadState::Loaded), ("b.png", LoadState::Loading)];
+        assert_eq!(progress(states.into_iter()), Progress::Loading(0.5));
+
+        let states = [("a.png", LoadState::Loaded), ("b.png", LoadState::Loaded)];
+        assert_eq!(progress(states.into_iter()), Progress::Done);
+
+        let states = [("a.png", LoadState::Failed), ("b.png", LoadState::Loading)];
+        assert_eq!(progress(states.into_iter()), Progress::Failed(vec!["a.png"]));
+    }
+}
@@ -0,0 +1,211 @@
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::display::MainCamera;
+use crate::fruits::boost::BoostUsedEvent;
+use crate::fruits::fruit::SliceEvent;
+use crate::fruits::penalty::WaveEvent;
+use crate::level::LevelUpdate;
+use crate::replay::Playback;
+use crate::settings::{Settings, Toggle};
+
+const WAVE_TRAUMA: f32 = 0.45;
+const BOOST_TRAUMA: f32 = 0.3;
+/// Trauma lost per second.
+const TRAUMA_DECAY: f32 = 1.4;
+const MAX_SHAKE_OFFSET: f32 = 14.;
+const MAX_SHAKE_ANGLE: f32 = 0.03;
+
+const HIT_STOP_MILLIS: u64 = 45;
+const HIT_STOP_PER_FRUIT: u64 = 15;
+const MAX_HIT_STOP_MILLIS: u64 = 120;
+
+const ZOOM_PUNCH: f32 = 0.06;
+const ZOOM_PUNCH_SECONDS: f32 = 0.35;
+
+
+pub struct CameraEffectsPlugin;
+
+impl Plugin for CameraEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(Trauma(0.))
+            .insert_resource(HitStop(Duration::ZERO))
+            .insert_resource(ZoomPunch(None))
+            .add_systems(Update, (add_trauma, shake, hit_stop, start_zoom_punch, zoom_punch))
+        ;
+    }
+}
+
+
+/// Shake strength from 0 to 1, the camera moves by its square.
+#[derive(Resource)]
+struct Trauma(f32);
+
+/// Real time left until the game clock runs again.
+#[derive(Resource)]
+struct HitStop(Duration);
+
+#[derive(Resource)]
+struct ZoomPunch(Option<Timer>);
+
+
+/// Camera offset and rotation for the given trauma, smooth over time.
+pub fn shake_offset(trauma: f32, seconds: f32) -> (Vec2, f32) {
+    let shake = trauma.clamp(0., 1.).powi(2);
+
+    let x = (seconds * 37.).sin() * (seconds * 11.).cos();
+    let y = (seconds * 43.).sin() * (seconds * 7.).cos();
+    let angle = (seconds * 29.).sin();
+
+    (Vec2::new(x, y) * MAX_SHAKE_OFFSET * shake, angle * MAX_SHAKE_ANGLE * shake)
+}
+
+
+/// Freeze length for a swing that sliced `fruits` at once, `None` for single fruits.
+pub fn hit_stop_duration(fruits: usize) -> Option<Duration> {
+    if fruits < 2 {
+        return None;
+    }
+
+    let millis = HIT_STOP_MILLIS + HIT_STOP_PER_FRUIT * (fruits as u64 - 2);
+    Some(Duration::from_millis(millis.min(MAX_HIT_STOP_MILLIS)))
+}
+
+
+/// Projection scale during the zoom punch, `progress` goes from 0 to 1.
+pub fn zoom_scale(progress: f32) -> f32 {
+    let progress = progress.clamp(0., 1.);
+    1. - ZOOM_PUNCH * (PI * progress).sin() * (1. - progress)
+}
+
+
+fn add_trauma(
+    settings: Res<Settings>,
+    mut trauma: ResMut<Trauma>,
+    mut waves: EventReader<WaveEvent>,
+    mut boosts: EventReader<BoostUsedEvent>
+) {
+    let added = waves.iter().count() as f32 * WAVE_TRAUMA + boosts.iter().count() as f32 * BOOST_TRAUMA;
+
+    if added > 0. && settings.is_on(Toggle::ScreenShake) {
+        trauma.0 = (trauma.0 + added).min(1.);
+    }
+}
+
+
+fn shake(time: Res<Time>, mut trauma: ResMut<Trauma>, mut cameras: Query<&mut Transform, With<MainCamera>>) {
+    if trauma.0 <= 0. && !trauma.is_changed() {
+        return;
+    }
+
+    // real time, the camera keeps shaking through hit-stops
+    trauma.0 = (trauma.0 - TRAUMA_DECAY * time.raw_delta_seconds()).max(0.);
+    let (offset, angle) = shake_offset(trauma.0, time.raw_elapsed_seconds());
+
+    for mut transform in &mut cameras {
+        transform.translation.x = offset.x;
+        transform.translation.y = offset.y;
+        transform.rotation = Quat::from_rotation_z(angle);
+    }
+}
+
+
+fn hit_stop(
+    settings: Res<Settings>,
+    playback: Option<Res<Playback>>,
+    mut time: ResMut<Time>,
+    mut stop: ResMut<HitStop>,
+    mut slices: EventReader<SliceEvent>
+) {
+    // a watched replay already has its hit-stops in the frame times
+    if playback.is_some() {
+        slices.clear();
+        return;
+    }
+
+    if !stop.0.is_zero() {
+        stop.0 = stop.0.saturating_sub(time.raw_delta());
+
+        if stop.0.is_zero() {
+            time.unpause();
+        }
+    }
+
+    let longest = slices
+        .iter()
+        .filter(|slice| !slice.boost)
+        .filter_map(|slice| hit_stop_duration(slice.fruits.len()))
+        .max();
+
+    if let Some(duration) = longest {
+        if settings.is_on(Toggle::HitStop) {
+            stop.0 = stop.0.max(duration);
+            time.pause();
+        }
+    }
+}
+
+
+fn start_zoom_punch(settings: Res<Settings>, mut punch: ResMut<ZoomPunch>, mut levels: EventReader<LevelUpdate>) {
+    if levels.iter().count() > 0 && settings.is_on(Toggle::ZoomPunch) {
+        punch.0 = Some(Timer::from_seconds(ZOOM_PUNCH_SECONDS, TimerMode::Once));
+    }
+}
+
+
+fn zoom_punch(
+    time: Res<Time>,
+    mut punch: ResMut<ZoomPunch>,
+    mut cameras: Query<&mut OrthographicProjection, With<MainCamera>>
+) {
+    let Some(timer) = punch.0.as_mut() else {
+        return;
+    };
+
+    timer.tick(time.raw_delta());
+    let scale = zoom_scale(timer.percent());
+
+    if timer.finished() {
+        punch.0 = None;
+    }
+
+    for mut projection in &mut cameras {
+        projection.scale = scale;
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::Vec2;
+    use super::{hit_stop_duration, shake_offset, zoom_scale, MAX_HIT_STOP_MILLIS};
+
+    #[test]
+    fn test_it_does_not_shake_without_trauma() {
+        assert_eq!(shake_offset(0., 1.3), (Vec2::ZERO, 0.));
+
+        let (weak, _) = shake_offset(0.3, 0.2);
+        let (strong, _) = shake_offset(0.9, 0.2);
+        assert!(strong.length() > weak.length());
+    }
+
+    #[test]
+    fn test_it_stops_only_on_multi_slices() {
+        assert_eq!(hit_stop_duration(1), None);
+        assert!(hit_stop_duration(3) > hit_stop_duration(2));
+        assert_eq!(hit_stop_duration(50), Some(Duration::from_millis(MAX_HIT_STOP_MILLIS)));
+    }
+
+    #[test]
+    fn test_it_returns_to_normal_zoom() {
+        assert!((zoom_scale(0.) - 1.).abs() < f32::EPSILON);
+        assert!(zoom_scale(0.3) < 1.);
+        assert!((zoom_scale(1.) - 1.).abs() < 1e-6);
+    }
+}
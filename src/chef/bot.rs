@@ -0,0 +1,285 @@
+use bevy::prelude::*;
+
+use crate::fruits::fruit::{Fruit, Sided};
+use crate::fruits::versus::Garbage;
+use crate::global::FLOOR_Y;
+use crate::states::session::Session;
+use crate::utils::random::{randint, GameRng};
+
+use super::input::ChefInput;
+use super::Player;
+
+
+/// Half width of the band around the chef where a swing still hits a fruit, with some margin.
+const REACH: f32 = 70.;
+/// How far a fruit can fall below the chef and still be hit.
+const BELOW_REACH: f32 = 110.;
+/// The chef swings once the lowest fruit is this close above it, so stacked fruits go in one swing.
+const SWING_HEIGHT: f32 = 40.;
+/// Close enough to the target to stop walking.
+const TOLERANCE: f32 = 30.;
+/// Fruits arriving sooner than this count as danger when deciding on a boost.
+const DANGER_TIME: f32 = 1.;
+const BOOST_COOLDOWN: f32 = 2.;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard
+}
+
+impl Difficulty {
+    #[cfg(test)]
+    pub const ALL: [Self; 3] = [Self::Easy, Self::Normal, Self::Hard];
+
+    /// Any of the difficulties, for chefs that are only there to be watched.
+    pub fn random() -> Self {
+        match randint(0, 3) {
+            0 => Self::Easy,
+            1 => Self::Normal,
+            _ => Self::Hard
+        }
+    }
+
+    /// Seconds between two looks at the playfield.
+    const fn reaction(self) -> f32 {
+        match self {
+            Self::Easy => 0.45,
+            Self::Normal => 0.2,
+            Self::Hard => 0.05
+        }
+    }
+
+    /// Largest distance the chef misjudges a fruit's x by.
+    const fn aim_error(self) -> f32 {
+        match self {
+            Self::Easy => 60.,
+            Self::Normal => 30.,
+            Self::Hard => 0.
+        }
+    }
+
+    const fn swing_cooldown(self) -> f32 {
+        match self {
+            Self::Easy => 0.45,
+            Self::Normal => 0.2,
+            Self::Hard => 0.05
+        }
+    }
+
+    /// Fruits about to be missed before a boost is fired, `None` never boosts.
+    const fn boost_danger(self) -> Option<usize> {
+        match self {
+            Self::Easy => None,
+            Self::Normal => Some(3),
+            Self::Hard => Some(1)
+        }
+    }
+}
+
+
+/// Chef driven by the computer, `read_input` leaves its `ChefInput` alone.
+#[derive(Component)]
+pub struct Bot {
+    difficulty: Difficulty,
+    target: Option<f32>,
+    since_look: f32,
+    since_swing: f32,
+    since_boost: f32,
+    /// Misjudges fruits apart from the run's `GameRng`, so a bot never changes which fruits fall.
+    aim: GameRng
+}
+
+impl Bot {
+    pub fn new(difficulty: Difficulty) -> Self {
+        Self {
+            difficulty,
+            target: None,
+            since_look: f32::MAX,
+            since_swing: f32::MAX,
+            since_boost: f32::MAX,
+            aim: GameRng::from_time()
+        }
+    }
+
+    /// Same misjudged fruits on every run with the same seed.
+    pub fn seeded(mut self, seed: u64) -> Self {
+        self.aim = GameRng::new(seed);
+        self
+    }
+}
+
+
+/// Seconds until a fruit at `y` reaches the chef's height, negative once it has passed.
+pub fn arrival(y: f32, fall_speed: f32) -> f32 {
+    (y - FLOOR_Y) / fall_speed
+}
+
+
+fn reachable(chef_x: f32, speed: f32, fruit: Vec2, fall_speed: f32) -> bool {
+    let walk = ((fruit.x - chef_x).abs() - REACH).max(0.) / speed;
+    walk <= arrival(fruit.y, fall_speed) + BELOW_REACH / fall_speed
+}
+
+
+/// X of the soonest arriving fruit the chef can still get under, fruits are `(position, fall speed)`.
+pub fn pick_target(chef_x: f32, speed: f32, fruits: &[(Vec2, f32)]) -> Option<f32> {
+    fruits
+        .iter()
+        .filter(|(position, fall_speed)| reachable(chef_x, speed, *position, *fall_speed))
+        .min_by(|(a, a_speed), (b, b_speed)| arrival(a.y, *a_speed).total_cmp(&arrival(b.y, *b_speed)))
+        .map(|(position, _)| position.x)
+}
+
+
+/// Number of fruits that will fall before the chef can get to them.
+pub fn danger(chef_x: f32, speed: f32, fruits: &[(Vec2, f32)]) -> usize {
+    fruits
+        .iter()
+        .filter(|(position, fall_speed)| arrival(position.y, *fall_speed) < DANGER_TIME)
+        .filter(|(position, fall_speed)| !reachable(chef_x, speed, *position, *fall_speed))
+        .count()
+}
+
+
+fn in_swing(chef_x: f32, fruit: Vec2) -> bool {
+    let height = fruit.y - FLOOR_Y;
+    (fruit.x - chef_x).abs() < REACH && height < SWING_HEIGHT && height > -BELOW_REACH
+}
+
+
+pub fn drive_bot(
+    time: Res<Time>,
+    session: Res<Session>,
+    mut bots: Query<(&mut Bot, &mut ChefInput, &Player, &Transform)>,
+    fruits: Query<(&Transform, &Fruit, Sided)>
+) {
+    let delta = time.delta_seconds();
+
+    for (mut bot, mut input, player, transform) in &mut bots {
+        let chef_x = transform.translation.x;
+        let difficulty = bot.difficulty;
+
+        let targets = fruits
+            .iter()
+            .filter(|(_, fruit, (side, garbage))| {
+                !fruit.sliced
+                    && garbage.copied() != Some(Garbage::Bomb)
+                    && side.is_none_or(|side| side.0 == player.id)
+            })
+            .map(|(transform, fruit, _)| (transform.translation.truncate(), fruit.fall_speed))
+            .filter(|(position, _)| position.y > FLOOR_Y - BELOW_REACH)
+            .collect::<Vec<_>>();
+
+        bot.since_look += delta;
+        bot.since_swing += delta;
+        bot.since_boost += delta;
+
+        if bot.since_look >= difficulty.reaction() {
+            bot.since_look = 0.;
+
+            let error = difficulty.aim_error();
+            let error = if error > 0. { bot.aim.uniform(-error, error) } else { 0. };
+            bot.target = pick_target(chef_x, player.speed(), &targets).map(|x| x + error);
+        }
+
+        let mut next = ChefInput::default();
+
+        if let Some(target) = bot.target {
+            if (target - chef_x).abs() > TOLERANCE {
+                next.direction = (target - chef_x).signum();
+            }
+        }
+
+        if bot.since_swing >= difficulty.swing_cooldown() && targets.iter().any(|(position, _)| in_swing(chef_x, *position)) {
+            bot.since_swing = 0.;
+            next.slice = true;
+        }
+
+        if let Some(threshold) = difficulty.boost_danger() {
+            if session.boosts > 0 && bot.since_boost >= BOOST_COOLDOWN && danger(chef_x, player.speed(), &targets) >= threshold {
+                bot.since_boost = 0.;
+                next.boost = true;
+            }
+        }
+
+        if *input != next {
+            *input = next;
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+
+    use crate::chef::Player;
+    use crate::global::GameplaySet;
+    use crate::replay::gameplay_app;
+    use crate::states::session::{GameMode, Session, MAX_LIVES};
+
+    use super::{drive_bot, pick_target, Bot, Difficulty};
+
+    const FRAME: Duration = Duration::from_millis(50);
+    const SEED: u64 = 2024;
+    /// Level 20 is two minutes in, far beyond what most players reach.
+    const LAST_LEVEL: u32 = 20;
+
+    /// Headless game with a single bot chef that never runs out of lives,
+    /// returns how many lives it lost on each level up to `LAST_LEVEL`.
+    fn play(difficulty: Difficulty) -> [u32; LAST_LEVEL as usize] {
+        let mut app = gameplay_app(GameMode::Solo, SEED, 1);
+
+        app
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+            .add_systems(Update, drive_bot.in_set(GameplaySet::Input));
+
+        let chef = app.world.query_filtered::<Entity, With<Player>>().single(&app.world);
+        app.world.entity_mut(chef).insert(Bot::new(difficulty).seeded(SEED));
+
+        let mut lost = [0; LAST_LEVEL as usize];
+
+        loop {
+            app.update();
+
+            let mut session = app.world.resource_mut::<Session>();
+            if session.level > LAST_LEVEL {
+                return lost;
+            }
+
+            lost[session.level as usize - 1] += MAX_LIVES - session.lives_left;
+            session.lives_left = MAX_LIVES;
+        }
+    }
+
+    #[test]
+    fn test_it_targets_the_first_reachable_fruit() {
+        let fruits = [
+            (Vec2::new(-300., 100.), 400.),
+            // arrives first but is too far to walk to in time
+            (Vec2::new(500., -150.), 400.),
+            (Vec2::new(200., 0.), 400.)
+        ];
+
+        assert_eq!(pick_target(0., 900., &fruits), Some(200.));
+        assert_eq!(pick_target(0., 900., &[]), None);
+    }
+
+    #[test]
+    fn test_it_estimates_difficulty_of_levels() {
+        let [easy, normal, hard] = Difficulty::ALL.map(play);
+        let report = format!("lives lost per level\neasy   {easy:?}\nnormal {normal:?}\nhard   {hard:?}");
+
+        let total = |lost: [u32; LAST_LEVEL as usize]| lost.iter().sum::<u32>();
+        assert!(total(hard) <= total(normal) && total(normal) <= total(easy), "{report}");
+        assert!(hard[..10].iter().all(|lost| *lost == 0), "{report}");
+        assert!(total(easy) > 0, "{report}");
+    }
+}
@@ -1,10 +1,16 @@
 use bevy::prelude::*;
 
 use super::sprite;
+use super::input::{ChefInput, Controls, COOP_CONTROLS, SOLO_CONTROLS};
+use super::skins::{SkinAtlases, Wardrobe};
 use super::ChefHitEvent;
 
-use self::sprite::AnimationSlice;
-use crate::level::LevelUpdate;
+use crate::animation::SpriteAnimation;
+use crate::fruits::versus::Side;
+use crate::level::{LevelUpdate, StartLevel};
+use crate::global::CHEF_LIMIT;
+use crate::states::session::{GameMode, MAX_PLAYERS};
+use crate::utils::save::SaveData;
 
 const SPEED: f32 = 900.;
 const SPEED_UPDATE: f32 = 10.;
@@ -12,66 +18,188 @@ const SPEED_UPDATE: f32 = 10.;
 
 #[derive(Component)]
 pub struct Player {
-    speed: f32
+    speed: f32,
+    pub id: usize,
+    /// Leftmost and rightmost x the chef can walk to.
+    bounds: (f32, f32)
 }
 
 
-pub fn setup(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
-) {
+impl Player {
+    pub fn new(mode: GameMode, id: usize) -> Self {
+        Self { speed: SPEED, id, bounds: bounds(mode, id) }
+    }
+
+    /// As fast as the chef gets by `level`.
+    pub fn at_level(mut self, level: u32) -> Self {
+        self.set_level(level);
+        self
+    }
+
+    pub fn set_level(&mut self, level: u32) {
+        self.speed = speed_at(level);
+    }
+
+    pub const fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub const fn bounds(&self) -> (f32, f32) {
+        self.bounds
+    }
+}
+
+
+fn speed_at(level: u32) -> f32 {
+    SPEED + SPEED_UPDATE * level.saturating_sub(1) as f32
+}
+
+
+/// Second chef is tinted so both players can tell their chefs apart.
+const PLAYER_TINTS: [Color; MAX_PLAYERS] = [Color::WHITE, Color::rgb(0.6, 0.8, 1.)];
+
+/// Where each chef starts when two are playing, inside their half in versus.
+const COOP_START_X: [f32; MAX_PLAYERS] = [-200., 200.];
+
+
+pub const fn player_tint(id: usize) -> Color {
+    PLAYER_TINTS[id % MAX_PLAYERS]
+}
+
+
+const fn controls(mode: GameMode, id: usize) -> Controls {
+    match mode {
+        GameMode::Solo => SOLO_CONTROLS,
+        GameMode::Coop | GameMode::Versus => COOP_CONTROLS[id % MAX_PLAYERS]
+    }
+}
+
+
+pub const fn start_x(mode: GameMode, id: usize) -> f32 {
+    if mode.players() > 1 { COOP_START_X[id % MAX_PLAYERS] } else { 0. }
+}
+
+
+fn bounds(mode: GameMode, id: usize) -> (f32, f32) {
+    match mode {
+        GameMode::Versus => Side(id).chef_bounds(CHEF_LIMIT),
+        GameMode::Solo | GameMode::Coop => (-CHEF_LIMIT, CHEF_LIMIT)
+    }
+}
+
 
-    let sprite = sprite::get_sprite(&asset_server, &mut texture_atlases);
-    let animation = sprite::AnimationSlice::new();
+fn spawn_chef(commands: &mut Commands, atlases: &SkinAtlases, wardrobe: &Wardrobe, mode: GameMode, id: usize) {
+    let skin = wardrobe.skin();
+
+    let mut sprite = sprite::get_sprite(atlases.0[wardrobe.skin].clone(), skin);
+    sprite.sprite.color = player_tint(id);
+    sprite.transform.translation.x = start_x(mode, id);
+    let animation = SpriteAnimation::new(sprite::clips(skin.frames));
 
     commands.spawn(
         (
             sprite, 
             animation, 
-            Player { speed: SPEED }, 
+            Player::new(mode, id), 
+            controls(mode, id),
+            ChefInput::default()
         )
     );
 }
 
 
-pub fn reset_speed(mut query: Query<&mut Player>) {
-    let player = &mut query.single_mut();
-    player.speed = SPEED;
+pub fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    save: Res<SaveData>,
+    mode: Res<GameMode>
+) {
+    let atlases = SkinAtlases::new(&asset_server, &mut texture_atlases);
+    let wardrobe = Wardrobe::load(&save);
+
+    spawn_chef(&mut commands, &atlases, &wardrobe, *mode, 0);
+
+    commands.insert_resource(atlases);
+    commands.insert_resource(wardrobe);
 }
 
 
-pub fn animate(
-    time: Res<Time>,
-    mut query: Query<(&mut TextureAtlasSprite, &mut AnimationSlice), With<Player>>,
+/// Spawns or removes the second chef and hands out key sets for the new mode.
+pub fn sync_players(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    atlases: Res<SkinAtlases>,
+    wardrobe: Res<Wardrobe>,
+    mut query: Query<(&mut Player, &mut Controls, &mut Transform, Entity)>
 ) {
-    for (mut sprite, mut animation) in query.iter_mut() {
-        animation.timer.tick(time.delta());
+    let players = mode.players();
 
-        if animation.timer.finished() {
-            animation.update();
-            sprite.index = animation.index;
+    for (mut player, mut player_controls, mut transform, entity) in &mut query {
+        if player.id >= players {
+            commands.entity(entity).despawn();
+            continue;
         }
 
+        *player_controls = controls(*mode, player.id);
+        player.bounds = bounds(*mode, player.id);
+        transform.translation.x = start_x(*mode, player.id);
+    }
+
+    let spawned = query.iter().filter(|(player, ..)| player.id < players).count();
+
+    for id in spawned..players {
+        spawn_chef(&mut commands, &atlases, &wardrobe, *mode, id);
+    }
+}
+
+
+pub fn apply_skin(
+    wardrobe: Res<Wardrobe>,
+    atlases: Res<SkinAtlases>,
+    mut query: Query<(&mut Handle<TextureAtlas>, &mut TextureAtlasSprite, &mut SpriteAnimation, &Player)>
+) {
+    if !wardrobe.is_changed() {
+        return;
+    }
+
+    let skin = wardrobe.skin();
+
+    for (mut texture_atlas, mut sprite, mut animation, player) in &mut query {
+        *texture_atlas = atlases.0[wardrobe.skin].clone();
+        sprite.color = player_tint(player.id);
+        *animation = SpriteAnimation::new(sprite::clips(skin.frames));
+        sprite.index = animation.index();
+    }
+}
+
+
+/// Every run starts from the same spot and speed, whatever the chef did on the menu.
+pub fn reset_chefs(mode: Res<GameMode>, start: Res<StartLevel>, mut query: Query<(&mut Player, &mut Transform)>) {
+    for (mut player, mut transform) in &mut query {
+        player.set_level(start.0);
+        transform.translation.x = start_x(*mode, player.id);
     }
 }
 
 
 pub fn walk(
-    keys: Res<Input<KeyCode>>, 
     time: Res<Time>, 
-    mut query: Query<(&mut Transform, &mut TextureAtlasSprite, &Player)>
+    mut query: Query<(&mut Transform, &mut TextureAtlasSprite, &Player, &ChefInput)>
 ) {
     for (
         mut transform, 
         mut sprite, 
-        player
+        player,
+        input
     ) in &mut query {
-        if keys.pressed(KeyCode::Left) && transform.translation.x > -500. {
+        let (left, right) = player.bounds();
+
+        if input.direction < 0. && transform.translation.x > left {
             sprite.flip_x = true;
             transform.translation.x -= player.speed * time.delta_seconds();                
         }
-        else if keys.pressed(KeyCode::Right) && transform.translation.x < 500. {
+        else if input.direction > 0. && transform.translation.x < right {
             sprite.flip_x = false;
             transform.translation.x += player.speed * time.delta_seconds();                
         }
@@ -80,43 +208,39 @@ pub fn walk(
 
 
 pub fn hit(
-    keys: Res<Input<KeyCode>>, 
     mut event: EventWriter<ChefHitEvent>,
-    mut query: Query<(&Transform, &mut AnimationSlice), With<Player>>,
+    mut query: Query<(&Transform, &mut SpriteAnimation, &Player, &ChefInput)>,
 ) { 
-    if keys.just_pressed(KeyCode::F) {
-        for (transform, mut animation) in &mut query {
+    for (transform, mut animation, player, input) in &mut query {
+        if input.slice {
             event.send(ChefHitEvent {
-                translation: transform.translation
+                translation: transform.translation,
+                player: player.id
             });
-            animation.trigger_slice();
+            animation.play("slice");
         }
     }
 }
 
 
-pub fn collect_rotten_fruits(
-    keys: Res<Input<KeyCode>>,
-    mut query: Query<&mut AnimationSlice, With<Player>>
-) {
-
-    if keys.pressed(KeyCode::S) || keys.just_pressed(KeyCode::S) {
-        for mut animation in &mut query {
-            animation.pullout_trash_bag();
+pub fn collect_rotten_fruits(mut query: Query<(&mut SpriteAnimation, &ChefInput), With<Player>>) {
+    for (mut animation, input) in &mut query {
+        if input.bag {
+            animation.play("bag");
         }
-    }
-    else if keys.just_released(KeyCode::S) {
-        for mut animation in &mut query {
-            animation.normal();
+        else if animation.clip().name == "bag" {
+            animation.play("idle");
         }
     }
 }
 
 
-pub fn update_level(events: EventReader<LevelUpdate>, mut query: Query<&mut Player>) {
-    if !events.is_empty() {
+pub fn update_level(mut events: EventReader<LevelUpdate>, mut query: Query<&mut Player>) {
+    let levels = events.iter().count();
+
+    if levels > 0 {
         for mut player in &mut query {
-            player.speed += SPEED_UPDATE;
-        }        
+            player.speed += SPEED_UPDATE * levels as f32;
+        }
     }
 }
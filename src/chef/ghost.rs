@@ -0,0 +1,322 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::animation::SpriteAnimation;
+use crate::global::{AppState, GameplaySet};
+use crate::replay::{expand, run_length, start_recording, ChosenSeed, Replay, MAX_RUN_FRAMES};
+use crate::settings::{Settings, Toggle};
+use crate::states::session::{GameMode, Session};
+use crate::utils::save::SaveData;
+
+use super::input::ChefInput;
+use super::skins::{SkinAtlases, Wardrobe};
+use super::sprite;
+use super::Player;
+
+/// The ghost's track keeps one sample per this many milliseconds.
+const SAMPLE_MS: u32 = 100;
+const GHOST_ALPHA: f32 = 0.35;
+/// Ghosts kept in the save, the seeds played longest ago go first.
+const MAX_GHOSTS: usize = 10;
+const GHOSTS_KEY: &str = "ghosts";
+
+
+const FROM_MENU_ENTER: OnTransition<AppState> = OnTransition::<AppState> {
+    from: AppState::MainMenu,
+    to: AppState::InGame
+};
+
+const FROM_FINISH_ENTER: OnTransition<AppState> = OnTransition::<AppState> {
+    from: AppState::Finish,
+    to: AppState::InGame
+};
+
+
+pub struct GhostPlugin;
+
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<GhostRun>()
+            // the seed of the new run is only known once the replay started recording
+            .add_systems(FROM_MENU_ENTER, (apply_deferred, setup).chain().after(start_recording))
+            .add_systems(FROM_FINISH_ENTER, (apply_deferred, setup).chain().after(start_recording))
+            .add_systems(OnEnter::<AppState>(AppState::Finish), remove_ghost)
+            // after the finish screen, so a run retried there counts as played on a chosen seed
+            .add_systems(OnExit::<AppState>(AppState::Finish), save_best)
+            .add_systems(Update, (record_track, move_ghost)
+                .chain()
+                .after(GameplaySet::Garbage)
+                .run_if(in_state(AppState::InGame).and_then(resource_equals(GameMode::Solo)))
+            )
+        ;
+    }
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Sample {
+    x: i32,
+    score: u32
+}
+
+
+/// Where the chef was and what it had scored over a run, with the times it sliced.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GhostTrack {
+    samples: Vec<Sample>,
+    /// Milliseconds into the run.
+    slices: Vec<u32>
+}
+
+impl GhostTrack {
+    pub fn serialize(&self) -> String {
+        let samples = run_length(self.samples.iter().map(|sample| format!("{}:{}", sample.x, sample.score)));
+        let slices = self.slices.iter().map(u32::to_string).collect::<Vec<_>>().join(",");
+
+        format!("{samples};{slices}")
+    }
+
+    pub fn parse(track: &str) -> Option<Self> {
+        let (samples, slices) = track.split_once(';')?;
+
+        let samples = expand(samples, MAX_RUN_FRAMES)?
+            .iter()
+            .map(|sample| {
+                let (x, score) = sample.split_once(':')?;
+                Some(Sample { x: x.parse().ok()?, score: score.parse().ok()? })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let slices = slices
+            .split(',')
+            .filter(|slice| !slice.is_empty())
+            .map(|slice| slice.parse().ok())
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self { samples, slices })
+    }
+
+    /// X of the chef `ms` into the run, in between samples it walks in a straight line.
+    pub fn x_at(&self, ms: u32) -> Option<f32> {
+        let index = (ms / SAMPLE_MS) as usize;
+        let from = self.samples.get(index).or_else(|| self.samples.last())?;
+        let to = self.samples.get(index + 1).unwrap_or(from);
+        let t = (ms % SAMPLE_MS) as f32 / SAMPLE_MS as f32;
+
+        Some(from.x as f32 + (to.x - from.x) as f32 * t)
+    }
+
+    /// Score of the run `ms` in, the final score once the track ends.
+    pub fn score_at(&self, ms: u32) -> u32 {
+        let index = (ms / SAMPLE_MS) as usize;
+        self.samples.get(index).or_else(|| self.samples.last()).map_or(0, |sample| sample.score)
+    }
+}
+
+
+/// The solo run being played, recorded on the way, and the best run on its seed if there is one.
+#[derive(Resource, Default)]
+pub struct GhostRun {
+    /// Time into the run, stops while the game is paused.
+    elapsed: Duration,
+    score: u32,
+    recording: GhostTrack,
+    best: Option<GhostTrack>
+}
+
+impl GhostRun {
+    /// Points ahead of the ghost, negative when behind, `None` without a ghost.
+    pub fn delta(&self, score: u32) -> Option<i64> {
+        let best = self.best.as_ref()?;
+        Some(i64::from(score) - i64::from(best.score_at(self.elapsed.as_millis() as u32)))
+    }
+}
+
+
+fn save_key(seed: u64) -> String {
+    format!("ghost.{seed}")
+}
+
+
+/// Keeps `track` as the ghost of `seed` when it beats the saved one, forgetting the oldest ghosts past `MAX_GHOSTS`.
+fn store_ghost(save: &mut SaveData, seed: u64, score: u32, track: &GhostTrack) {
+    let key = save_key(seed);
+    let best = save.get(&key).and_then(|saved| saved.split_once(';')?.0.parse::<u32>().ok());
+
+    if best.is_some_and(|best| score <= best) {
+        return;
+    }
+    save.set(&key, format!("{score};{}", track.serialize()));
+
+    let seed = seed.to_string();
+    let mut seeds = save.get_list(GHOSTS_KEY).into_iter().filter(|saved| *saved != seed).map(String::from).collect::<Vec<_>>();
+    seeds.push(seed);
+    let kept = seeds.split_off(seeds.len().saturating_sub(MAX_GHOSTS));
+    save.set(GHOSTS_KEY, kept.join(","));
+
+    // also drops ghosts of every random seed saved before they were capped
+    save.retain(|key| key.strip_prefix("ghost.").is_none_or(|seed| kept.iter().any(|kept| kept == seed)));
+}
+
+
+#[derive(Component)]
+struct Ghost;
+
+
+fn setup(
+    mut commands: Commands,
+    save: Res<SaveData>,
+    settings: Res<Settings>,
+    mode: Res<GameMode>,
+    replay: Res<Replay>,
+    atlases: Res<SkinAtlases>,
+    wardrobe: Res<Wardrobe>,
+    ghosts: Query<Entity, With<Ghost>>
+) {
+    for entity in &ghosts {
+        commands.entity(entity).despawn();
+    }
+
+    let best = save
+        .get(&save_key(replay.seed))
+        .filter(|_| *mode == GameMode::Solo && settings.is_on(Toggle::Ghost))
+        .and_then(|saved| GhostTrack::parse(saved.split_once(';')?.1));
+
+    if best.is_some() {
+        let skin = wardrobe.skin();
+        let mut ghost = sprite::get_sprite(atlases.0[wardrobe.skin].clone(), skin);
+        ghost.sprite.color = Color::WHITE.with_a(GHOST_ALPHA);
+        // just behind the real chef
+        ghost.transform.translation.z -= 0.1;
+
+        commands.spawn((ghost, SpriteAnimation::new(sprite::clips(skin.frames)), Ghost));
+    }
+
+    commands.insert_resource(GhostRun { best, ..default() });
+}
+
+
+fn record_track(
+    time: Res<Time>,
+    session: Res<Session>,
+    mut run: ResMut<GhostRun>,
+    chefs: Query<(&Transform, &Player, &ChefInput)>
+) {
+    run.elapsed += time.delta();
+    run.score = session.score;
+    let ms = run.elapsed.as_millis() as u32;
+
+    for (transform, player, input) in &chefs {
+        if player.id != 0 {
+            continue;
+        }
+
+        if input.slice {
+            run.recording.slices.push(ms);
+        }
+
+        while run.recording.samples.len() <= (ms / SAMPLE_MS) as usize {
+            let sample = Sample { x: transform.translation.x.round() as i32, score: session.score };
+            run.recording.samples.push(sample);
+        }
+    }
+}
+
+
+fn move_ghost(
+    time: Res<Time>,
+    run: Res<GhostRun>,
+    mut ghosts: Query<(&mut Transform, &mut TextureAtlasSprite, &mut SpriteAnimation), With<Ghost>>
+) {
+    let Some(best) = &run.best else {
+        return;
+    };
+
+    let ms = run.elapsed.as_millis() as u32;
+    let last = run.elapsed.saturating_sub(time.delta()).as_millis() as u32;
+    let Some(x) = best.x_at(ms) else {
+        return;
+    };
+
+    for (mut transform, mut sprite, mut animation) in &mut ghosts {
+        if (x - transform.translation.x).abs() > f32::EPSILON {
+            sprite.flip_x = x < transform.translation.x;
+        }
+        transform.translation.x = x;
+
+        if best.slices.iter().any(|slice| (last..ms).contains(slice)) {
+            animation.play("slice");
+        }
+    }
+}
+
+
+/// Keeps the track of a solo run when it beats the best one on the same seed.
+/// Only chosen seeds get a ghost, a fresh random seed is never played again.
+fn save_best(mut save: ResMut<SaveData>, mode: Res<GameMode>, replay: Res<Replay>, chosen: Res<ChosenSeed>, run: Res<GhostRun>) {
+    if *mode != GameMode::Solo || chosen.0 != Some(replay.seed) || run.recording.samples.is_empty() {
+        return;
+    }
+
+    store_ghost(&mut save, replay.seed, run.score, &run.recording);
+}
+
+
+fn remove_ghost(mut commands: Commands, ghosts: Query<Entity, With<Ghost>>) {
+    for entity in &ghosts {
+        commands.entity(entity).despawn();
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::save::SaveData;
+    use super::{store_ghost, GhostTrack, Sample, MAX_GHOSTS};
+
+    #[test]
+    fn test_it_follows_a_saved_track() {
+        let track = GhostTrack {
+            samples: vec![
+                Sample { x: 0, score: 0 },
+                Sample { x: 100, score: 0 },
+                Sample { x: 100, score: 3 },
+                Sample { x: 100, score: 3 }
+            ],
+            slices: vec![150]
+        };
+
+        let parsed = GhostTrack::parse(&track.serialize()).unwrap();
+        assert_eq!(parsed, track);
+        assert_eq!(track.serialize(), "0:0,100:0,2*100:3;150");
+
+        assert_eq!(parsed.x_at(50), Some(50.));
+        assert_eq!(parsed.x_at(10_000), Some(100.));
+        assert_eq!(parsed.score_at(120), 0);
+        assert_eq!(parsed.score_at(10_000), 3);
+        assert_eq!(GhostTrack::default().x_at(0), None);
+    }
+
+    #[test]
+    fn test_it_keeps_only_the_latest_ghosts() {
+        let mut save = SaveData::parse("ghost.1=5;0:0;\nghost.99=3;0:0;\nsettings.ghost=1");
+        let track = GhostTrack { samples: vec![Sample { x: 0, score: 4 }], slices: vec![] };
+
+        store_ghost(&mut save, 1, 4, &track);
+        assert_eq!(save.get("ghost.1"), Some("5;0:0;"));
+
+        for seed in 1..=MAX_GHOSTS as u64 + 1 {
+            store_ghost(&mut save, seed, 9, &track);
+        }
+
+        // the first seed and the ghost saved before the cap are gone
+        assert_eq!(save.get("ghost.1"), None);
+        assert_eq!(save.get("ghost.99"), None);
+        assert_eq!(save.get("ghost.2"), Some("9;0:4;"));
+        assert_eq!(save.get_list("ghosts").len(), MAX_GHOSTS);
+        assert_eq!(save.get("settings.ghost"), Some("1"));
+    }
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+
+use super::Player;
+use super::bot::Bot;
+
+/// Stick deflection below this is ignored.
+const STICK_DEADZONE: f32 = 0.3;
+
+
+/// Keyboard layout of one chef, gamepads follow the player id.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Controls {
+    pub left: KeyCode,
+    pub right: KeyCode,
+    pub slice: KeyCode,
+    pub bag: KeyCode,
+    pub boost: KeyCode
+}
+
+pub const SOLO_CONTROLS: Controls = Controls {
+    left: KeyCode::Left,
+    right: KeyCode::Right,
+    slice: KeyCode::F,
+    bag: KeyCode::S,
+    boost: KeyCode::A
+};
+
+/// Two players on one keyboard: left hand and arrows.
+pub const COOP_CONTROLS: [Controls; 2] = [
+    Controls { left: KeyCode::A, right: KeyCode::D, slice: KeyCode::F, bag: KeyCode::S, boost: KeyCode::W },
+    Controls { left: KeyCode::Left, right: KeyCode::Right, slice: KeyCode::ShiftRight, bag: KeyCode::Down, boost: KeyCode::Up }
+];
+
+
+/// What a chef does this frame, filled from its controls.
+#[derive(Component, Default, Clone, Copy, Debug, PartialEq)]
+pub struct ChefInput {
+    /// -1 walks left, 1 walks right.
+    pub direction: f32,
+    pub slice: bool,
+    /// Held for as long as the trash bag stays out.
+    pub bag: bool,
+    pub boost: bool
+}
+
+
+pub fn read_input(
+    keys: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    buttons: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut query: Query<(&Player, &Controls, &mut ChefInput), Without<Bot>>
+) {
+    for (player, controls, mut input) in &mut query {
+        let mut next = ChefInput::default();
+
+        if keys.pressed(controls.left) {
+            next.direction -= 1.;
+        }
+        if keys.pressed(controls.right) {
+            next.direction += 1.;
+        }
+        next.slice = keys.just_pressed(controls.slice);
+        next.bag = keys.pressed(controls.bag);
+        next.boost = keys.just_pressed(controls.boost);
+
+        if let Some(gamepad) = gamepads.iter().nth(player.id) {
+            let button = |button_type| GamepadButton::new(gamepad, button_type);
+
+            let stick = axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX)).unwrap_or(0.);
+            if stick.abs() > STICK_DEADZONE {
+                next.direction += stick.signum();
+            }
+            if buttons.pressed(button(GamepadButtonType::DPadLeft)) {
+                next.direction -= 1.;
+            }
+            if buttons.pressed(button(GamepadButtonType::DPadRight)) {
+                next.direction += 1.;
+            }
+
+            next.slice |= buttons.just_pressed(button(GamepadButtonType::South));
+            next.bag |= buttons.pressed(button(GamepadButtonType::West));
+            next.boost |= buttons.just_pressed(button(GamepadButtonType::North));
+        }
+
+        next.direction = next.direction.clamp(-1., 1.);
+
+        if *input != next {
+            *input = next;
+        }
+    }
+}
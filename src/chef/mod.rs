@@ -1,11 +1,23 @@
 use bevy::prelude::*;
 
-use crate::states::session;
-use crate::global::AppState;
+use crate::states::session::{self, GameMode};
+use crate::global::{AppState, GameplaySet};
 
-mod sprite;
-mod chef;
+pub mod sprite;
+pub mod chef;
+pub mod trail;
+pub mod skins;
+pub mod input;
+pub mod bot;
+pub mod ghost;
 
+pub use chef::Player;
+
+
+const FROM_MENU_ENTER: OnTransition<AppState> = OnTransition::<AppState> {
+    from: AppState::MainMenu,
+    to: AppState::InGame
+};
 
 const FROM_FINISH_ENTER: OnTransition<AppState> = OnTransition::<AppState> {
     from: AppState::Finish,
@@ -17,14 +29,21 @@ pub struct ChefPlugin;
 impl Plugin for ChefPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(FROM_FINISH_ENTER, chef::reset_speed)
+            .add_plugins(ghost::GhostPlugin)
+            .add_systems(FROM_MENU_ENTER, chef::reset_chefs)
+            .add_systems(FROM_FINISH_ENTER, chef::reset_chefs)
+            .add_systems(OnExit::<AppState>(AppState::Demo), chef::reset_chefs)
             .add_systems(Startup, chef::setup.after(session::setup))
+            // before the state changes, so a run starts with every chef of the new mode in place
+            .add_systems(PreUpdate, chef::sync_players.run_if(resource_changed::<GameMode>()))
             .add_systems(Update, (
-                chef::hit, 
-                chef::walk, 
-                chef::collect_rotten_fruits, 
-                chef::animate, 
-                chef::update_level
+                (input::read_input, bot::drive_bot).chain().in_set(GameplaySet::Input),
+                (chef::walk, chef::hit).chain().in_set(GameplaySet::Act).run_if(not(in_state(AppState::Paused))),
+                chef::collect_rotten_fruits.after(GameplaySet::Input),
+                chef::update_level.in_set(GameplaySet::Spawn),
+                chef::apply_skin,
+                trail::spawn_trail,
+                trail::fade_trail,
             ))
             .add_event::<ChefHitEvent>();
     }
@@ -33,5 +52,6 @@ impl Plugin for ChefPlugin {
 
 #[derive(Event)]
 pub struct ChefHitEvent {
-    pub translation: Vec3
+    pub translation: Vec3,
+    pub player: usize
 }
@@ -0,0 +1,271 @@
+use bevy::prelude::*;
+
+use crate::achievements::ACHIEVEMENTS_KEY;
+use crate::utils::save::SaveData;
+
+
+const SKIN_KEY: &str = "skin";
+const TRAIL_KEY: &str = "trail";
+const UNLOCKED_KEY: &str = "unlocked";
+
+
+/// Which frames of a chef sprite sheet belong to which animation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameMap {
+    pub slice: (usize, usize),
+    pub idle: (usize, usize),
+    pub bag: usize
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unlock {
+    Free,
+    Score(u32),
+    Achievement(&'static str)
+}
+
+
+pub struct Skin {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub image: &'static str,
+    pub columns: usize,
+    pub tile: Vec2,
+    pub frames: FrameMap,
+    pub unlock: Unlock
+}
+
+
+pub struct Trail {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub color: Color,
+    pub width: f32,
+    pub unlock: Unlock
+}
+
+
+pub const SKINS: [Skin; 4] = [
+    Skin {
+        id: "classic",
+        name: "Classic",
+        image: "images/chef2.png",
+        columns: 17,
+        tile: Vec2::new(40., 40.),
+        frames: FrameMap { slice: (2, 7), idle: (9, 16), bag: 8 },
+        unlock: Unlock::Free
+    },
+    Skin {
+        id: "night",
+        name: "Night shift",
+        image: "images/chef-night.png",
+        columns: 15,
+        tile: Vec2::new(40., 40.),
+        frames: FrameMap { slice: (8, 13), idle: (0, 7), bag: 14 },
+        unlock: Unlock::Score(50)
+    },
+    Skin {
+        id: "lime",
+        name: "Lime",
+        image: "images/chef-lime.png",
+        columns: 15,
+        tile: Vec2::new(40., 40.),
+        frames: FrameMap { slice: (0, 5), idle: (7, 14), bag: 6 },
+        unlock: Unlock::Score(150)
+    },
+    Skin {
+        id: "golden",
+        name: "Golden",
+        image: "images/chef-golden.png",
+        columns: 15,
+        tile: Vec2::new(40., 40.),
+        frames: FrameMap { slice: (9, 14), idle: (1, 8), bag: 0 },
+        unlock: Unlock::Score(300)
+    },
+];
+
+pub const TRAILS: [Trail; 4] = [
+    Trail {
+        id: "steel",
+        name: "Steel",
+        color: Color::rgba(1., 1., 1., 0.8),
+        width: 6.,
+        unlock: Unlock::Free
+    },
+    Trail {
+        id: "flame",
+        name: "Flame",
+        color: Color::rgba(1., 0.45, 0.1, 0.9),
+        width: 9.,
+        unlock: Unlock::Score(100)
+    },
+    Trail {
+        id: "frost",
+        name: "Frost",
+        color: Color::rgba(0.5, 0.9, 1., 0.9),
+        width: 9.,
+        unlock: Unlock::Score(200)
+    },
+    Trail {
+        id: "juice",
+        name: "Juice",
+        color: Color::rgba(1., 0.2, 0.5, 0.9),
+        width: 12.,
+        unlock: Unlock::Achievement("triple")
+    },
+];
+
+
+/// Currently equipped cosmetics, indexes into `SKINS` and `TRAILS`.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Wardrobe {
+    pub skin: usize,
+    pub trail: usize
+}
+
+impl Wardrobe {
+    pub fn load(save: &SaveData) -> Self {
+        let skin = save.get(SKIN_KEY)
+            .and_then(|id| SKINS.iter().position(|skin| skin.id == id))
+            .unwrap_or(0);
+
+        let trail = save.get(TRAIL_KEY)
+            .and_then(|id| TRAILS.iter().position(|trail| trail.id == id))
+            .unwrap_or(0);
+
+        Self { skin, trail }
+    }
+
+    pub fn store(&self, save: &mut SaveData) {
+        save.set(SKIN_KEY, SKINS[self.skin].id);
+        save.set(TRAIL_KEY, TRAILS[self.trail].id);
+    }
+
+    pub const fn skin(&self) -> &'static Skin {
+        &SKINS[self.skin]
+    }
+
+    pub const fn trail(&self) -> &'static Trail {
+        &TRAILS[self.trail]
+    }
+}
+
+
+pub fn is_unlocked(save: &SaveData, id: &str, unlock: Unlock) -> bool {
+    unlock == Unlock::Free || save.contains(UNLOCKED_KEY, id)
+}
+
+
+/// Marks every cosmetic whose requirement is met as unlocked.
+/// Returns names of the newly unlocked ones.
+pub fn unlock_cosmetics(save: &mut SaveData, score: u32) -> Vec<&'static str> {
+    unlock_where(save, |save, unlock| match unlock {
+        Unlock::Free => false,
+        Unlock::Score(required) => score >= required,
+        Unlock::Achievement(achievement) => save.contains(ACHIEVEMENTS_KEY, achievement)
+    })
+}
+
+
+/// Unlocks the cosmetics of achievements as soon as they are recorded, without waiting for the run to end.
+pub fn unlock_achievement_cosmetics(save: &mut SaveData) -> Vec<&'static str> {
+    unlock_where(save, |save, unlock| match unlock {
+        Unlock::Achievement(achievement) => save.contains(ACHIEVEMENTS_KEY, achievement),
+        Unlock::Free | Unlock::Score(_) => false
+    })
+}
+
+
+fn unlock_where(save: &mut SaveData, reached: impl Fn(&SaveData, Unlock) -> bool) -> Vec<&'static str> {
+    let requirements = SKINS.iter()
+        .map(|skin| (skin.id, skin.name, skin.unlock))
+        .chain(TRAILS.iter().map(|trail| (trail.id, trail.name, trail.unlock)));
+
+    let mut unlocked = Vec::new();
+
+    for (id, name, unlock) in requirements {
+        if reached(save, unlock) && save.push(UNLOCKED_KEY, id) {
+            unlocked.push(name);
+        }
+    }
+
+    unlocked
+}
+
+
+#[derive(Resource)]
+pub struct SkinAtlases(pub Vec<Handle<TextureAtlas>>);
+
+impl SkinAtlases {
+    pub fn new(asset_server: &Res<AssetServer>, texture_atlases: &mut ResMut<Assets<TextureAtlas>>) -> Self {
+        let handles = SKINS.iter()
+            .map(|skin| {
+                let texture_atlas = TextureAtlas::from_grid(
+                    asset_server.load(skin.image),
+                    skin.tile,
+                    skin.columns,
+                    1,
+                    None,
+                    None
+                );
+                texture_atlases.add(texture_atlas)
+            })
+            .collect();
+
+        Self(handles)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::save::SaveData;
+    use super::{unlock_achievement_cosmetics, unlock_cosmetics, is_unlocked, Wardrobe, SKINS, TRAILS};
+
+    #[test]
+    fn test_it_unlocks_by_score() {
+        let mut save = SaveData::default();
+
+        let unlocked = unlock_cosmetics(&mut save, 120);
+        assert_eq!(unlocked, vec!["Night shift", "Flame"]);
+
+        assert!(is_unlocked(&save, SKINS[1].id, SKINS[1].unlock));
+        assert!(!is_unlocked(&save, SKINS[3].id, SKINS[3].unlock));
+
+        // already unlocked cosmetics are not reported twice
+        assert!(unlock_cosmetics(&mut save, 120).is_empty());
+    }
+
+    #[test]
+    fn test_it_unlocks_by_achievement() {
+        let mut save = SaveData::default();
+        save.push("achievements", "triple");
+
+        assert_eq!(unlock_achievement_cosmetics(&mut save), vec!["Juice"]);
+        assert!(is_unlocked(&save, TRAILS[3].id, TRAILS[3].unlock));
+    }
+
+    #[test]
+    fn test_it_restores_wardrobe() {
+        let mut save = SaveData::default();
+        Wardrobe { skin: 2, trail: 1 }.store(&mut save);
+
+        let wardrobe = Wardrobe::load(&save);
+
+        assert_eq!(wardrobe.skin().id, SKINS[2].id);
+        assert_eq!(wardrobe.trail().id, TRAILS[1].id);
+    }
+
+    #[test]
+    fn test_it_maps_frames_inside_each_sheet() {
+        for skin in &SKINS {
+            let frames = skin.frames;
+            let last = frames.slice.1.max(frames.idle.1).max(frames.bag);
+
+            assert!(frames.slice.0 <= frames.slice.1 && frames.idle.0 <= frames.idle.1, "{}", skin.id);
+            assert!(last < skin.columns, "{}", skin.id);
+        }
+    }
+}
@@ -1,91 +1,33 @@
 use bevy::prelude::*;
 
-use crate::components::Clock;
+use crate::animation::{Clip, OnFinished, PlayMode};
+use crate::global::FLOOR_Y;
 
+use super::skins::{FrameMap, Skin};
 
-pub fn get_sprite(asset_server: &Res<AssetServer>, texture_atlases: &mut ResMut<Assets<TextureAtlas>>) -> SpriteSheetBundle {
-    let texture_atlas =TextureAtlas::from_grid(
-        asset_server.load("images/chef2.png"), 
-        Vec2::new(40.0, 40.0),
-        17, 
-        1, 
-        None,
-        None
-    );
-
-    let texture_atlas_handle = texture_atlases.add(texture_atlas);
 
+pub fn get_sprite(texture_atlas: Handle<TextureAtlas>, skin: &Skin) -> SpriteSheetBundle {
     SpriteSheetBundle {
-        texture_atlas: texture_atlas_handle,
-        sprite: TextureAtlasSprite::new(0),
-        transform: Transform::from_xyz(0.0, -220.0, 1.0).with_scale(Vec3::splat(5.0)),
+        texture_atlas,
+        sprite: TextureAtlasSprite {
+            index: skin.frames.idle.0,
+            ..default()
+        },
+        transform: Transform::from_xyz(0.0, FLOOR_Y, 1.0).with_scale(Vec3::splat(5.0)),
         ..default()
     }
 }
 
 
-#[derive(PartialEq, Eq, Debug)]
-pub enum ChefState {
-    Slice,
-    Waiting,
-    Bag
-}
-
-
-#[derive(Component)]
-pub struct AnimationSlice {
-    state: ChefState,
-    pub index: usize,
-    pub timer: Clock,
-}
-
-impl AnimationSlice {
-    pub fn normal(&mut self) {
-        self.state = ChefState::Waiting;
-        self.index = 9;
-    }
-
-    pub fn trigger_slice(&mut self) {
-        self.state = ChefState::Slice;
-        self.index = 2;
-    }
-
-    pub fn pullout_trash_bag(&mut self) {
-        self.state = ChefState::Bag;
-        self.index = 8;
-    }
+/// Duration of every chef frame in milliseconds.
+const FRAME_TIME: &[u64] = &[70];
 
-    pub fn update(&mut self) {
-        match self.state {
-            ChefState::Waiting => {
-                if self.index == 16 {
-                    self.index = 9;
-                } else {
-                    self.index += 1;
-                }
-            },
-            ChefState::Slice => {
-                if self.index == 0 {
-                    self.index = 1;
-                }
-                if self.index < 7 {
-                    self.index += 1;
-                } else {
-                    self.normal();
-                } 
-            },
-            ChefState::Bag => {
-                self.index = 8;
-            }
-        }
-    }
-
-    pub fn new() -> Self {
-        Self {
-            state: ChefState::Waiting,
-            index: 9,
-            timer: Clock::seconds(0.07),
-        }
-    }
 
+/// Chef animation clips for a sprite sheet layout, idle plays first.
+pub const fn clips(frames: FrameMap) -> [Clip; 3] {
+    [
+        Clip::new("idle", frames.idle.0, frames.idle.1, FRAME_TIME).mode(PlayMode::Loop),
+        Clip::new("slice", frames.slice.0, frames.slice.1, FRAME_TIME).then(OnFinished::Play("idle")),
+        Clip::new("bag", frames.bag, frames.bag, FRAME_TIME).mode(PlayMode::Loop)
+    ]
 }
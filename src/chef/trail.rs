@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+
+use crate::components::Clock;
+use crate::particles::{spawn_emitter, EmitterSpec};
+
+use super::skins::{Trail, Wardrobe};
+use super::ChefHitEvent;
+
+const TRAIL_LENGTH: f32 = 180.;
+const TRAIL_LIFETIME: u64 = 150;
+
+
+const SPARKS: EmitterSpec = EmitterSpec {
+    burst: 12,
+    lifetime: (0.15, 0.3),
+    speed: (200., 450.),
+    spread: 1.4,
+    gravity: -900.,
+    color: (Color::rgb(1., 0.95, 0.7), Color::rgba(1., 0.5, 0.1, 0.)),
+    size: (5., 2.),
+    ..EmitterSpec::DEFAULT
+};
+
+
+#[derive(Component)]
+pub struct BladeTrail;
+
+
+pub fn spawn_trail(
+    mut commands: Commands,
+    mut events: EventReader<ChefHitEvent>,
+    wardrobe: Res<Wardrobe>
+) {
+    for event in &mut events {
+        spawn_blade(&mut commands, wardrobe.trail(), event.translation);
+    }
+}
+
+
+/// Blade swing with sparks above a chef standing at `chef`.
+pub fn spawn_blade(commands: &mut Commands, trail: &Trail, chef: Vec3) {
+    let transform = Transform::from_xyz(chef.x, chef.y + 60., chef.z + 1.)
+        .with_rotation(Quat::from_rotation_z(30_f32.to_radians()));
+
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: trail.color,
+                custom_size: Some(Vec2::new(TRAIL_LENGTH, trail.width)),
+                ..default()
+            },
+            transform,
+            ..default()
+        },
+        BladeTrail,
+        Clock::millis_once(TRAIL_LIFETIME)
+    ));
+
+    let blade = Vec3::new(chef.x, chef.y + 60., chef.z + 2.);
+    spawn_emitter(commands, SPARKS, blade);
+}
+
+
+pub fn fade_trail(
+    mut commands: Commands,
+    time: Res<Time>,
+    wardrobe: Res<Wardrobe>,
+    mut query: Query<(&mut Sprite, &mut Transform, &mut Clock, Entity), With<BladeTrail>>
+) {
+    let alpha = wardrobe.trail().color.a();
+
+    for (mut sprite, mut transform, mut clock, entity) in &mut query {
+        clock.tick(time.delta());
+        transform.rotate_z(-8. * time.delta_seconds());
+
+        let left = 1. - clock.percent();
+        sprite.color.set_a(alpha * left);
+
+        if clock.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use bevy::log::Level;
+use bevy::prelude::*;
+use bevy::window::WindowMode;
+
+use crate::level::MAX_LEVEL;
+use crate::states::session::GameMode;
+
+
+pub const USAGE: &str = "\
+usage: fruits [options]
+       fruits verify [file]     check a record by playing its run again
+
+options:
+  --seed <n>                 seed of the runs
+  --mode <solo|coop|versus>  mode Enter starts on the main menu
+  --level <n>                start every run at this level
+  --windowed, --fullscreen
+  --resolution <width>x<height>
+  --mute                     start with every sound off
+  --replay <file>            watch a recorded run
+  --record <file>            also save each finished run's replay to this file
+  --headless                 play --replay, or a bot run, without a window and print the score
+  --data-dir <dir>           where saves and records are kept
+  --log-level <level>        error, warn, info, debug or trace, RUST_LOG still wins
+  --help";
+
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Command {
+    #[default]
+    Play,
+    Verify(PathBuf),
+    Help
+}
+
+
+/// Command line options, inserted as a resource for the plugins that use them.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct Cli {
+    pub command: Command,
+    pub seed: Option<u64>,
+    pub mode: Option<GameMode>,
+    pub level: Option<u32>,
+    pub window_mode: Option<WindowMode>,
+    pub resolution: Option<Vec2>,
+    pub mute: bool,
+    pub replay: Option<PathBuf>,
+    pub record: Option<PathBuf>,
+    pub headless: bool,
+    pub data_dir: Option<PathBuf>,
+    pub log_level: Option<Level>
+}
+
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CliError {
+    Unknown(String),
+    /// The option is missing its value or the value doesn't parse.
+    Value(String)
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Unknown(arg) => write!(f, "unknown argument {arg}"),
+            Self::Value(option) => write!(f, "bad or missing value for --{option}")
+        }
+    }
+}
+
+
+fn parse_resolution(value: &str) -> Option<Vec2> {
+    let (width, height) = value.split_once('x')?;
+    let resolution = Vec2::new(width.parse().ok()?, height.parse().ok()?);
+    (resolution.min_element() > 0.).then_some(resolution)
+}
+
+
+/// Parses the arguments after the program name, options take `--name value` or `--name=value`.
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Cli, CliError> {
+    let mut cli = Cli::default();
+    let mut args = args.into_iter().peekable();
+
+    if args.peek().map(String::as_str) == Some("verify") {
+        args.next();
+        let path = args.next().unwrap_or_else(|| crate::utils::record::FILE_NAME.to_string());
+        cli.command = Command::Verify(path.into());
+        return args.next().map_or(Ok(cli), |arg| Err(CliError::Unknown(arg)));
+    }
+
+    while let Some(arg) = args.next() {
+        let (name, mut inline) = match arg.split_once('=') {
+            Some((name, value)) => (name.to_string(), Some(value.to_string())),
+            None => (arg.clone(), None)
+        };
+
+        let Some(option) = name.strip_prefix("--") else {
+            return Err(CliError::Unknown(arg));
+        };
+
+        let invalid = || CliError::Value(option.to_string());
+        let mut value = || inline.take().or_else(|| args.next()).ok_or_else(invalid);
+
+        match option {
+            "help" => cli.command = Command::Help,
+            "windowed" => cli.window_mode = Some(WindowMode::Windowed),
+            "fullscreen" => cli.window_mode = Some(WindowMode::BorderlessFullscreen),
+            "mute" => cli.mute = true,
+            "headless" => cli.headless = true,
+            "seed" => cli.seed = Some(value()?.parse().map_err(|_| invalid())?),
+            "mode" => cli.mode = Some(GameMode::from_name(&value()?).ok_or_else(invalid)?),
+            "level" => cli.level = Some(value()?.parse().ok().filter(|level| (1..=MAX_LEVEL).contains(level)).ok_or_else(invalid)?),
+            "resolution" => cli.resolution = Some(parse_resolution(&value()?).ok_or_else(invalid)?),
+            "replay" => cli.replay = Some(value()?.into()),
+            "record" => cli.record = Some(value()?.into()),
+            "data-dir" => cli.data_dir = Some(value()?.into()),
+            "log-level" => cli.log_level = Some(value()?.parse().map_err(|_| invalid())?),
+            _ => return Err(CliError::Unknown(arg))
+        }
+    }
+
+    Ok(cli)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::log::Level;
+    use bevy::prelude::Vec2;
+    use bevy::window::WindowMode;
+
+    use crate::states::session::GameMode;
+    use super::{parse, CliError, Command};
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_it_parses_options() {
+        let cli = parse(args("--seed 42 --mode=coop --level 5 --fullscreen --resolution 1280x720 --mute --log-level debug")).unwrap();
+
+        assert_eq!(cli.command, Command::Play);
+        assert_eq!(cli.seed, Some(42));
+        assert_eq!(cli.mode, Some(GameMode::Coop));
+        assert_eq!(cli.level, Some(5));
+        assert_eq!(cli.window_mode, Some(WindowMode::BorderlessFullscreen));
+        assert_eq!(cli.resolution, Some(Vec2::new(1280., 720.)));
+        assert!(cli.mute && !cli.headless);
+        assert_eq!(cli.log_level, Some(Level::DEBUG));
+
+        assert_eq!(parse(args("verify run.txt")).unwrap().command, Command::Verify("run.txt".into()));
+        assert_eq!(parse(args("--help")).unwrap().command, Command::Help);
+    }
+
+    #[test]
+    fn test_it_rejects_bad_arguments() {
+        assert_eq!(parse(args("--seed")), Err(CliError::Value("seed".to_string())));
+        assert_eq!(parse(args("--mode ranked")), Err(CliError::Value("mode".to_string())));
+        assert_eq!(parse(args("--level 0")), Err(CliError::Value("level".to_string())));
+        assert_eq!(parse(args("--level 4000000000")), Err(CliError::Value("level".to_string())));
+        assert_eq!(parse(args("--resolution 1280")), Err(CliError::Value("resolution".to_string())));
+        assert_eq!(parse(args("--speed 2")), Err(CliError::Unknown("--speed".to_string())));
+        assert_eq!(parse(args("play")), Err(CliError::Unknown("play".to_string())));
+    }
+}
@@ -22,13 +22,6 @@ impl Clock {
         }
     }
 
-    pub fn millis(millis: u64) -> Self {
-        Self {
-            timer: Timer::new(Duration::from_millis(millis), TimerMode::Repeating)
-        }
-    }
-
-    #[allow(dead_code)]
     pub fn millis_once(millis: u64) -> Self {
         Self {
             timer: Timer::new(Duration::from_millis(millis), TimerMode::Once)
@@ -43,7 +36,7 @@ impl Clock {
         self.timer.finished()
     }
 
-    pub fn just_finished(&self) -> bool {
-        self.timer.just_finished()
+    pub fn percent(&self) -> f32 {
+        self.timer.percent()
     }
 }
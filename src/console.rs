@@ -0,0 +1,276 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::{ButtonState, InputSystem};
+use bevy::prelude::*;
+
+use crate::global::AppState;
+
+const TOGGLE_KEY: KeyCode = KeyCode::Grave;
+/// Lines of output kept on screen above the prompt.
+const LOG_LINES: usize = 12;
+const FONT_SIZE: f32 = 22.;
+
+const STATES: [AppState; 12] = [
+    AppState::Loading,
+    AppState::MainMenu,
+    AppState::InGame,
+    AppState::Paused,
+    AppState::Finish,
+    AppState::Wardrobe,
+    AppState::Achievements,
+    AppState::Stats,
+    AppState::Settings,
+    AppState::Tutorial,
+    AppState::Demo,
+    AppState::Leaderboard
+];
+
+
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Console>()
+            .add_systems(Startup, setup)
+            // before anything reads the keyboard, so typing doesn't also walk the chef
+            .add_systems(PreUpdate, type_line.after(InputSystem))
+            .add_systems(Update, (run_commands, show_console).chain())
+            .add_console_command("help", "help", help)
+            .add_console_command("timescale", "timescale <ratio>", timescale)
+            .add_console_command("state", "state <name>", state)
+        ;
+    }
+}
+
+
+/// Runs a console command with the words typed after its name, the `Ok` text is printed back.
+pub type Handler = fn(&mut World, &[&str]) -> Result<String, String>;
+
+
+struct ConsoleCommand {
+    usage: &'static str,
+    handler: Handler
+}
+
+
+/// Every command the console knows, plugins add theirs with [`ConsoleExt`].
+#[derive(Resource, Default)]
+pub struct ConsoleCommands(BTreeMap<&'static str, ConsoleCommand>);
+
+
+pub trait ConsoleExt {
+    fn add_console_command(&mut self, name: &'static str, usage: &'static str, handler: Handler) -> &mut Self;
+}
+
+impl ConsoleExt for App {
+    fn add_console_command(&mut self, name: &'static str, usage: &'static str, handler: Handler) -> &mut Self {
+        self.init_resource::<ConsoleCommands>();
+        self.world.resource_mut::<ConsoleCommands>().0.insert(name, ConsoleCommand { usage, handler });
+        self
+    }
+}
+
+
+/// Parses the argument at `index` of a command.
+pub fn arg<T: FromStr>(args: &[&str], index: usize) -> Result<T, String> {
+    let arg = args.get(index).ok_or("missing argument")?;
+    arg.parse().map_err(|_| format!("bad argument {arg}"))
+}
+
+
+#[derive(Resource, Default)]
+struct Console {
+    open: bool,
+    line: String,
+    log: Vec<String>,
+    /// Lines entered this frame, run once the world is free.
+    pending: Vec<String>
+}
+
+
+#[derive(Component)]
+struct ConsoleRoot;
+
+#[derive(Component)]
+struct ConsoleText;
+
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let root = NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.),
+            bottom: Val::Px(0.),
+            padding: UiRect::all(Val::Px(8.)),
+            ..default()
+        },
+        background_color: Color::rgba(0., 0., 0., 0.75).into(),
+        visibility: Visibility::Hidden,
+        z_index: ZIndex::Global(100),
+        ..default()
+    };
+
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/mn-regular.otf"),
+        font_size: FONT_SIZE,
+        color: Color::WHITE
+    };
+
+    commands.spawn((root, ConsoleRoot)).with_children(|root| {
+        root.spawn((TextBundle::from_section("", text_style), ConsoleText));
+    });
+}
+
+
+fn type_line(
+    mut console: ResMut<Console>,
+    mut keyboard: EventReader<KeyboardInput>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut keys: ResMut<Input<KeyCode>>
+) {
+    for event in &mut keyboard {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match event.key_code {
+            Some(TOGGLE_KEY) => console.open = !console.open,
+            Some(KeyCode::Return) if console.open => {
+                let line = std::mem::take(&mut console.line);
+                console.pending.push(line);
+            }
+            Some(KeyCode::Back) if console.open => {
+                console.line.pop();
+            }
+            _ => {}
+        }
+    }
+
+    for character in &mut characters {
+        if console.open && character.char != '`' && !character.char.is_control() {
+            console.line.push(character.char);
+        }
+    }
+
+    if console.open {
+        keys.reset_all();
+    }
+}
+
+
+fn run_commands(world: &mut World) {
+    let pending = std::mem::take(&mut world.resource_mut::<Console>().pending);
+
+    for line in pending {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let Some((name, args)) = words.split_first() else {
+            continue;
+        };
+
+        let command = world.resource::<ConsoleCommands>().0.get(name).map(|command| (command.usage, command.handler));
+        let output = match command {
+            Some((usage, handler)) => handler(world, args).unwrap_or_else(|error| format!("{error}, usage: {usage}")),
+            None => format!("unknown command {name}, try help")
+        };
+
+        let mut console = world.resource_mut::<Console>();
+        console.log.push(format!("> {line}"));
+        console.log.extend(output.lines().map(String::from));
+    }
+}
+
+
+fn show_console(
+    console: Res<Console>,
+    mut roots: Query<&mut Visibility, With<ConsoleRoot>>,
+    mut texts: Query<&mut Text, With<ConsoleText>>
+) {
+    if !console.is_changed() {
+        return;
+    }
+
+    for mut visibility in &mut roots {
+        *visibility = if console.open { Visibility::Visible } else { Visibility::Hidden };
+    }
+
+    let from = console.log.len().saturating_sub(LOG_LINES);
+    let log = console.log[from..].iter().fold(String::new(), |log, line| log + line + "\n");
+
+    for mut text in &mut texts {
+        text.sections[0].value = format!("{log}> {}_", console.line);
+    }
+}
+
+
+// every handler returns a `Result`, even the ones that can't fail
+#[allow(clippy::unnecessary_wraps)]
+fn help(world: &mut World, _: &[&str]) -> Result<String, String> {
+    let commands = world.resource::<ConsoleCommands>();
+    Ok(commands.0.values().map(|command| command.usage).collect::<Vec<_>>().join("\n"))
+}
+
+
+fn timescale(world: &mut World, args: &[&str]) -> Result<String, String> {
+    let ratio: f32 = arg(args, 0)?;
+    if !ratio.is_finite() || ratio < 0. {
+        return Err(format!("bad argument {ratio}"));
+    }
+
+    world.resource_mut::<Time>().set_relative_speed(ratio);
+    Ok(format!("time runs at {ratio}x"))
+}
+
+
+fn state(world: &mut World, args: &[&str]) -> Result<String, String> {
+    let name: String = arg(args, 0)?;
+    let state = STATES
+        .into_iter()
+        .find(|state| format!("{state:?}").eq_ignore_ascii_case(&name))
+        .ok_or_else(|| format!("unknown state {name}"))?;
+
+    world.resource_mut::<NextState<AppState>>().set(state.clone());
+    Ok(format!("switching to {state:?}"))
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::{arg, run_commands, Console, ConsoleExt};
+
+    fn double(world: &mut World, args: &[&str]) -> Result<String, String> {
+        let number: u32 = arg(args, 0)?;
+        world.insert_resource(Doubled(number * 2));
+        Ok(format!("{}", number * 2))
+    }
+
+    #[derive(Resource)]
+    struct Doubled(u32);
+
+    #[test]
+    fn test_it_runs_registered_commands() {
+        let mut app = App::new();
+        app
+            .init_resource::<Console>()
+            .add_console_command("double", "double <n>", double)
+            .add_systems(Update, run_commands);
+
+        app.world.resource_mut::<Console>().pending = vec!["double 21".into(), "double x".into(), "triple 1".into()];
+        app.update();
+
+        assert_eq!(app.world.resource::<Doubled>().0, 42);
+        assert_eq!(app.world.resource::<Console>().log, [
+            "> double 21",
+            "42",
+            "> double x",
+            "bad argument x, usage: double <n>",
+            "> triple 1",
+            "unknown command triple, try help"
+        ]);
+    }
+}
@@ -0,0 +1,160 @@
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::ecs::entity::Entities;
+use bevy::prelude::*;
+
+use crate::chef::Player;
+use crate::console::ConsoleExt;
+use crate::fruits::fruit::{Fruit, CHEF_HITBOX, DESPAWN_FLOOR, FRUIT_HITBOX};
+use crate::fruits::spawn::{SpawnTimer, FRUITS_SPAWN_BORDERS};
+use crate::fruits::versus::SIDE_SPAWN_BORDERS;
+use crate::global::{FLOOR_Y, SPAWN_Y, VIRTUAL_HEIGHT, VIRTUAL_WIDTH};
+use crate::states::session::{GameMode, Session};
+
+const TOGGLE_KEY: KeyCode = KeyCode::F3;
+const FONT_SIZE: f32 = 20.;
+
+const FRUIT_COLOR: Color = Color::GREEN;
+const SLICED_COLOR: Color = Color::GRAY;
+const CHEF_COLOR: Color = Color::CYAN;
+const SPAWN_COLOR: Color = Color::YELLOW;
+const FLOOR_COLOR: Color = Color::RED;
+const LIMIT_COLOR: Color = Color::ORANGE;
+
+
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+
+        app
+            .init_resource::<DebugOverlay>()
+            .add_systems(Startup, setup)
+            .add_systems(Update, (
+                toggle,
+                (draw_zones, draw_hitboxes, update_panel).run_if(resource_equals(DebugOverlay(true))),
+                show_panel.run_if(resource_changed::<DebugOverlay>())
+            ))
+            .add_console_command("debug", "debug", debug_command)
+        ;
+    }
+}
+
+
+/// Hitboxes, spawn zones and a panel of counters drawn over the game.
+#[derive(Resource, Default, PartialEq, Eq)]
+struct DebugOverlay(bool);
+
+
+#[derive(Component)]
+struct DebugPanel;
+
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/mn-regular.otf"),
+        font_size: FONT_SIZE,
+        color: Color::WHITE
+    };
+
+    let panel = TextBundle::from_section("", text_style)
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            left: Val::Px(16.),
+            bottom: Val::Px(16.),
+            ..default()
+        })
+        .with_background_color(Color::rgba(0., 0., 0., 0.6));
+
+    commands.spawn((panel, Visibility::Hidden, DebugPanel));
+}
+
+
+fn toggle(keys: Res<Input<KeyCode>>, mut overlay: ResMut<DebugOverlay>) {
+    if keys.just_pressed(TOGGLE_KEY) {
+        overlay.0 = !overlay.0;
+    }
+}
+
+
+fn show_panel(overlay: Res<DebugOverlay>, mut panels: Query<&mut Visibility, With<DebugPanel>>) {
+    for mut visibility in &mut panels {
+        *visibility = if overlay.0 { Visibility::Visible } else { Visibility::Hidden };
+    }
+}
+
+
+/// Where fruits appear, where they're dropped and how far each chef can walk.
+fn draw_zones(mut gizmos: Gizmos, mode: Res<GameMode>, chefs: Query<&Player>) {
+    let borders = if *mode == GameMode::Versus { SIDE_SPAWN_BORDERS.to_vec() } else { vec![FRUITS_SPAWN_BORDERS] };
+
+    for (left, right) in borders {
+        let (left, right) = (left as f32, right as f32);
+        gizmos.line_2d(Vec2::new(left, SPAWN_Y), Vec2::new(right, SPAWN_Y), SPAWN_COLOR);
+        gizmos.line_2d(Vec2::new(left, SPAWN_Y), Vec2::new(left, -VIRTUAL_HEIGHT / 2.), SPAWN_COLOR);
+        gizmos.line_2d(Vec2::new(right, SPAWN_Y), Vec2::new(right, -VIRTUAL_HEIGHT / 2.), SPAWN_COLOR);
+    }
+
+    gizmos.line_2d(Vec2::new(-VIRTUAL_WIDTH / 2., DESPAWN_FLOOR), Vec2::new(VIRTUAL_WIDTH / 2., DESPAWN_FLOOR), FLOOR_COLOR);
+
+    for player in &chefs {
+        let (left, right) = player.bounds();
+        let height = CHEF_HITBOX.y;
+
+        gizmos.line_2d(Vec2::new(left, FLOOR_Y - height), Vec2::new(left, FLOOR_Y + height), LIMIT_COLOR);
+        gizmos.line_2d(Vec2::new(right, FLOOR_Y - height), Vec2::new(right, FLOOR_Y + height), LIMIT_COLOR);
+        gizmos.line_2d(Vec2::new(left, FLOOR_Y), Vec2::new(right, FLOOR_Y), LIMIT_COLOR);
+    }
+}
+
+
+/// The same boxes `fruit::hit` checks a swing with.
+fn draw_hitboxes(
+    mut gizmos: Gizmos,
+    fruits: Query<(&Transform, &Fruit)>,
+    chefs: Query<&Transform, With<Player>>
+) {
+    for (transform, fruit) in &fruits {
+        let color = if fruit.sliced { SLICED_COLOR } else { FRUIT_COLOR };
+        gizmos.rect_2d(transform.translation.truncate(), 0., FRUIT_HITBOX, color);
+    }
+
+    for transform in &chefs {
+        gizmos.rect_2d(transform.translation.truncate(), 0., CHEF_HITBOX, CHEF_COLOR);
+    }
+}
+
+
+fn update_panel(
+    diagnostics: Res<DiagnosticsStore>,
+    entities: &Entities,
+    session: Res<Session>,
+    spawn_timer: Option<Res<SpawnTimer>>,
+    fruits: Query<(), With<Fruit>>,
+    mut panels: Query<&mut Text, With<DebugPanel>>
+) {
+    let fps = diagnostics
+        .get(FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(bevy::diagnostic::Diagnostic::smoothed)
+        .unwrap_or_default();
+    let spawn_timer = spawn_timer.map_or(0, |timer| timer.0.duration().as_millis());
+
+    for mut text in &mut panels {
+        text.sections[0].value = format!(
+            "fps {fps:.0}\nentities {}\nfruits {}\nspawn every {spawn_timer} ms\nlevel {}",
+            entities.len(),
+            fruits.iter().count(),
+            session.level
+        );
+    }
+}
+
+
+#[allow(clippy::unnecessary_wraps)]
+fn debug_command(world: &mut World, _: &[&str]) -> Result<String, String> {
+    let mut overlay = world.resource_mut::<DebugOverlay>();
+    overlay.0 = !overlay.0;
+    Ok(format!("debug overlay {}", if overlay.0 { "on" } else { "off" }))
+}
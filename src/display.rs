@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+use bevy::render::camera::{ScalingMode, Viewport};
+use bevy::window::{PrimaryWindow, WindowMode, WindowResized};
+
+use crate::global::{VIRTUAL_WIDTH, VIRTUAL_HEIGHT};
+
+
+pub struct DisplayPlugin;
+
+impl Plugin for DisplayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(ClearColor(Color::BLACK))
+            .add_systems(Startup, setup)
+            .add_systems(Update, (fit_viewport, toggle_fullscreen))
+        ;
+    }
+}
+
+
+#[derive(Component)]
+pub struct MainCamera;
+
+
+fn setup(mut commands: Commands) {
+    let mut camera = Camera2dBundle::default();
+    camera.projection.scaling_mode = ScalingMode::Fixed {
+        width: VIRTUAL_WIDTH,
+        height: VIRTUAL_HEIGHT
+    };
+
+    commands.spawn((camera, MainCamera));
+}
+
+
+/// Largest rectangle with the playfield aspect ratio that fits into the window,
+/// centered, as `(position, size)` in physical pixels.
+pub fn letterbox(window: UVec2) -> (UVec2, UVec2) {
+    // minimized windows report a zero size
+    let window = window.max(UVec2::ONE);
+    let scale = (window.x as f32 / VIRTUAL_WIDTH).min(window.y as f32 / VIRTUAL_HEIGHT);
+
+    let size = UVec2::new(
+        ((VIRTUAL_WIDTH * scale).round() as u32).clamp(1, window.x),
+        ((VIRTUAL_HEIGHT * scale).round() as u32).clamp(1, window.y)
+    );
+
+    ((window - size) / 2, size)
+}
+
+
+fn fit_viewport(
+    mut events: EventReader<WindowResized>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut cameras: Query<&mut Camera, With<MainCamera>>,
+    mut ui_scale: ResMut<UiScale>
+) {
+    if events.is_empty() {
+        return;
+    }
+    events.clear();
+
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+
+    let (position, size) = letterbox(UVec2::new(window.physical_width(), window.physical_height()));
+
+    for mut camera in &mut cameras {
+        camera.viewport = Some(Viewport {
+            physical_position: position,
+            physical_size: size,
+            ..default()
+        });
+    }
+
+    // keep the HUD proportional to the playfield
+    ui_scale.scale = f64::from((window.width() / VIRTUAL_WIDTH).min(window.height() / VIRTUAL_HEIGHT));
+}
+
+
+fn toggle_fullscreen(keys: Res<Input<KeyCode>>, mut windows: Query<&mut Window, With<PrimaryWindow>>) {
+    if !keys.just_pressed(KeyCode::F11) {
+        return;
+    }
+
+    for mut window in &mut windows {
+        window.mode = match window.mode {
+            WindowMode::Windowed => WindowMode::BorderlessFullscreen,
+            _ => WindowMode::Windowed
+        };
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::UVec2;
+    use super::letterbox;
+
+    #[test]
+    fn test_it_fits_native_resolution() {
+        assert_eq!(letterbox(UVec2::new(1140, 660)), (UVec2::ZERO, UVec2::new(1140, 660)));
+    }
+
+    #[test]
+    fn test_it_adds_bars() {
+        // wider window, bars on the sides
+        let (position, size) = letterbox(UVec2::new(1920, 660));
+        assert_eq!(size, UVec2::new(1140, 660));
+        assert_eq!(position, UVec2::new(390, 0));
+
+        // taller window, bars on the top and bottom
+        let (position, size) = letterbox(UVec2::new(570, 1000));
+        assert_eq!(size, UVec2::new(570, 330));
+        assert_eq!(position, UVec2::new(0, 335));
+    }
+}
@@ -1,14 +1,18 @@
 use bevy::prelude::*;
 
+use crate::animation::{AnimationFinished, Clip, OnFinished, SpriteAnimation};
+use crate::chef::Player;
+use crate::chef::input::ChefInput;
 use crate::components::Clock;
-use crate::global::AppState;
+use crate::global::{playing, GameplaySet};
 use crate::sound::{SoundEvent, SoundType};
 use crate::states::session::Session;
 
 use super::text::TextEvent;
 use super::splash::SplashEvent;
-use super::fruit::{Fruit, FruitType, start_slice_animation, DESPAWN_FLOOR};
-use super::sprite::create_boost_shot;
+use super::fruit::{Fruit, FruitType, SliceEvent, start_slice_animation, DESPAWN_FLOOR};
+use super::pool::{acquire, release, Active, Idle};
+use super::sprite::{create_boost_shot, EffectAtlases};
 
 
 pub struct BoostPlugin;
@@ -16,8 +20,13 @@ pub struct BoostPlugin;
 impl Plugin for BoostPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(Update, (spawn_boost, process_boost, spawn_boost_shot, animate_boost_shot).run_if(in_state(AppState::InGame)))
+            .add_systems(Update, (
+                (spawn_boost, process_boost).chain().in_set(GameplaySet::Boost),
+                spawn_boost_shot,
+                release_boost_shot
+            ).run_if(playing))
             .add_event::<BoostEvent>()
+            .add_event::<BoostUsedEvent>()
         ;
     }
 }
@@ -25,34 +34,49 @@ impl Plugin for BoostPlugin {
 
 #[derive(Component)]
 pub struct Boost {
-    count: usize
+    count: usize,
+    /// Chef who fired the boost, gets the points.
+    player: usize
 }
 
 #[derive(Component)]
-struct BoostShot;
+pub struct BoostShot;
+
+const BOOST_SHOT_CLIP: Clip = Clip::new("shot", 0, 4, &[80]).then(OnFinished::Event);
+
+
+/// Sent when the player spends a boost.
+#[derive(Event)]
+pub struct BoostUsedEvent;
 
 
 #[derive(Event)]
 pub struct BoostEvent {
-    point1: Vec3,
-    point2: Vec3
+    pub point1: Vec3,
+    pub point2: Vec3
 }
 
 
 pub fn spawn_boost(
     mut commands: Commands, 
     mut session: ResMut<Session>,
-    keys: Res<Input<KeyCode>>,
+    chefs: Query<(&Player, &ChefInput)>,
     query: Query<(&Transform, &Fruit)>,
     mut sound_event_writer: EventWriter<SoundEvent>,
+    mut boost_used: EventWriter<BoostUsedEvent>,
 ) {
-    if keys.just_pressed(KeyCode::A) && session.boosts > 0 {
+    for (player, input) in &chefs {
+        if !input.boost || session.boosts == 0 {
+            continue;
+        }
+
         let count = query.iter().filter(|(t, _)| t.translation.y < 300.).count() + 5;
 
         sound_event_writer.send(SoundEvent::sound(SoundType::BOOST));
 
-        commands.spawn((Boost {count}, Clock::seconds(0.1)));
+        commands.spawn((Boost { count, player: player.id }, Clock::seconds(0.1)));
         session.boosts -= 1;
+        boost_used.send(BoostUsedEvent);
     }
 }
 
@@ -67,6 +91,7 @@ pub fn process_boost(
     mut sound: EventWriter<SoundEvent>,
     mut text: EventWriter<TextEvent>,
     mut splash: EventWriter<SplashEvent>,
+    mut slice: EventWriter<SliceEvent>,
     mut boost_shot: EventWriter<BoostEvent>
 ) {
     for (mut boost, mut clock, boost_entity) in &mut boosts {
@@ -83,7 +108,7 @@ pub fn process_boost(
             .min_by(|(_, t1, _), (_, t2, _)| t1.translation.y.partial_cmp(&t2.translation.y).unwrap());
 
         if let Some((mut fruit, transform, entity)) = result {
-            session.score += 1;
+            session.add_score(boost.player, 1);
             boost.count -= 1;
             
             boost_shot.send(BoostEvent { 
@@ -119,8 +144,15 @@ pub fn process_boost(
                 fruit_type: fruit.fruit_type.clone()
             });
 
-            sound.send(SoundEvent::fruit_sound(fruit.fruit_type.clone()));
-            sound.send(SoundEvent::sound(SoundType::BOOST_HIT));
+            slice.send(SliceEvent {
+                fruits: vec![fruit.fruit_type.clone()],
+                boost: true,
+                player: boost.player
+            });
+
+            let position = transform.translation.truncate();
+            sound.send(SoundEvent::fruit_sound(fruit.fruit_type.clone()).at(position));
+            sound.send(SoundEvent::sound(SoundType::BOOST_HIT).at(position));
         }
 
         if boost.count == 0 {
@@ -132,10 +164,12 @@ pub fn process_boost(
 
 pub fn spawn_boost_shot(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    atlases: Res<EffectAtlases>,
     mut event_reader: EventReader<BoostEvent>,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>, 
+    idle: Query<Entity, (With<BoostShot>, With<Idle>)>
 ){
+    let mut idle = idle.iter();
+
     for event in event_reader.iter() {
         let x1 = event.point1.x;
         let x2 = event.point2.x;
@@ -147,35 +181,21 @@ pub fn spawn_boost_shot(
 
         let angle = (y2 - y1).atan2(x2 - x1);
 
-        let sprite = create_boost_shot(
-            &asset_server, 
-            &mut texture_atlases, 
-            x, 
-            y - 400.,
-            angle
-        );
+        let sprite = create_boost_shot(&atlases, x, y - 400., angle);
         
-        commands.spawn((BoostShot, Clock::seconds(0.08), sprite));        
+        acquire(&mut commands, &mut idle, (BoostShot, SpriteAnimation::new([BOOST_SHOT_CLIP]), sprite));
     }
 }
 
 
-fn animate_boost_shot(
-    time: Res<Time>,
-    mut query: Query<(&mut TextureAtlasSprite, &mut Clock, Entity), With<BoostShot>>,
-    mut commands: Commands
+pub fn release_boost_shot(
+    mut commands: Commands,
+    mut events: EventReader<AnimationFinished>,
+    query: Query<(), Active<BoostShot>>
 ) {
-    for (mut sprite, mut clock, entity) in query.iter_mut() {
-        clock.tick(time.delta());
-        if !clock.finished() {
-            continue;
+    for event in &mut events {
+        if query.contains(event.entity) {
+            release(&mut commands, event.entity);
         }
-
-        if sprite.index == 4 {
-            commands.entity(entity).despawn();
-            continue;
-        }
-
-        sprite.index += 1;
     }
 }
@@ -1,17 +1,18 @@
 use bevy::{prelude::*, sprite::collide_aabb::collide};
 
-use crate::global::AppState;
+use crate::global::{playing, AppState, GameplaySet, VIRTUAL_HEIGHT};
 use crate::utils::random::randint;
 use crate::chef::ChefHitEvent;
+use crate::level::StartLevel;
 use crate::sound::{SoundEvent, SoundType};
-use crate::states::session::Session;
-use crate::components::Clock;
+use crate::states::session::{Session, MAX_LIVES};
+use crate::animation::{Clip, OnFinished, SpriteAnimation};
 
 use super::penalty::WaveEvent;
-use super::sprite::FruitTextures;
 use super::spawn::SpawnTimer;
 use super::splash::SplashEvent;
 use super::text::TextEvent;
+use super::versus::{Garbage, Side};
 
 
 const FROM_MENU_ENTER: OnTransition<AppState> = OnTransition::<AppState> {
@@ -32,15 +33,15 @@ impl Plugin for FruitPlugin {
         app
             .add_systems(FROM_MENU_ENTER, setup)
             .add_systems(FROM_FINISH_ENTER,setup)
+            .add_systems(OnEnter::<AppState>(AppState::Demo), setup)
             .add_systems(
                 Update, 
                 (
-                    fall,
-                    despawn_fallen_fruits,
-                    hit, 
-                    animate_slice, 
-                ).run_if(in_state(AppState::InGame))
+                    hit.in_set(GameplaySet::Slice),
+                    (fall, despawn_fallen_fruits).chain().in_set(GameplaySet::Fall)
+                ).run_if(playing)
             )
+            .add_event::<SliceEvent>()
         ;
     }
 }
@@ -48,11 +49,15 @@ impl Plugin for FruitPlugin {
 
 
 const FALL_SPEED: f32 = 400.;
-const SLICE_ANIMATION_SPEED: u64 = 80;
-pub const DESPAWN_FLOOR: f32 = -480.;
+/// Fruit sheets cut the fruit in two over frames 0 to 7.
+const SLICE_CLIP: Clip = Clip::new("slice", 0, 7, &[80]).then(OnFinished::Remove);
+pub const DESPAWN_FLOOR: f32 = -VIRTUAL_HEIGHT / 2. - 150.;
+/// Boxes a chef swing has to overlap a fruit with to slice it.
+pub const FRUIT_HITBOX: Vec2 = Vec2::new(140., 220.);
+pub const CHEF_HITBOX: Vec2 = Vec2::new(40., 40.);
 
 
-#[derive(PartialEq, Eq, Debug, Clone)]
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
 pub enum FruitType {
     APPLE,
     STRAWBERRY,
@@ -63,6 +68,30 @@ pub enum FruitType {
     POME
 }
 
+impl FruitType {
+    pub const ALL: [Self; 7] = [
+        Self::APPLE, 
+        Self::STRAWBERRY, 
+        Self::ORANGE, 
+        Self::WATERMELON, 
+        Self::PINEAPPLE, 
+        Self::BANANA, 
+        Self::POME
+    ];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::APPLE => "apple",
+            Self::STRAWBERRY => "strawberry",
+            Self::ORANGE => "orange",
+            Self::WATERMELON => "watermelon",
+            Self::PINEAPPLE => "pineapple",
+            Self::BANANA => "banana",
+            Self::POME => "pome"
+        }
+    }
+}
+
 
 #[derive(Component, Clone, Debug)]
 pub struct Fruit {
@@ -95,18 +124,25 @@ impl Fruit {
 }
 
 
-#[derive(Component)]
-pub struct HitAnimation;
+/// Versus side and garbage kind, both missing outside versus.
+pub type Sided = (Option<&'static Side>, Option<&'static Garbage>);
+
+
+/// Sent once per chef swing or boost shot that sliced at least one fruit.
+#[derive(Event)]
+pub struct SliceEvent {
+    pub fruits: Vec<FruitType>,
+    pub boost: bool,
+    pub player: usize
+}
 
 
 pub fn setup(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    start: Res<StartLevel>,
     query: Query<Entity, With<Fruit>>
 ) {
-    commands.insert_resource(FruitTextures::new(&asset_server, &mut texture_atlases));
-    commands.insert_resource(SpawnTimer::new());
+    commands.insert_resource(SpawnTimer::at_level(start.0));
 
     // cleanup fruits on restart
     for entity in &query {
@@ -122,26 +158,48 @@ pub fn hit(
     mut sound: EventWriter<SoundEvent>,
     mut splash: EventWriter<SplashEvent>,
     mut text: EventWriter<TextEvent>,
+    mut slice: EventWriter<SliceEvent>,
     
-    mut query: Query<(&Transform, Entity, &mut Fruit)>,
+    mut query: Query<(&Transform, Entity, &mut Fruit, Sided)>,
     mut session: ResMut<Session>,
 ) {
     for event in events.iter() {
-        let mut hitted_fruits = Vec::<Fruit>::new();
+        let mut hitted_fruits = Vec::<(Fruit, Vec2)>::new();
 
-        for (transform, entity, mut fruit) in &mut query {
+        for (transform, entity, mut fruit, (side, garbage)) in &mut query {
+            // versus chefs only reach fruits on their own half
+            if side.is_some_and(|side| side.0 != event.player) {
+                continue;
+            }
+            let side = side.map(|side| side.0);
 
             let successfull_hit = collide(
-                transform.translation, Vec2::new(140., 220.), 
-                event.translation, Vec2::new(40., 40.), 
+                transform.translation, FRUIT_HITBOX, 
+                event.translation, CHEF_HITBOX, 
             ).is_some();
 
-            if successfull_hit {
+            if successfull_hit && !fruit.sliced && garbage.is_some() {
+                start_slice_animation(&mut commands, &entity);
+                fruit.slice();
+
+                if garbage == Some(&Garbage::Bomb) {
+                    let lives = session.lives_mut(side);
+                    *lives = lives.saturating_sub(1);
+
+                    text.send(TextEvent{
+                        text: "-1 live!".to_string(), 
+                        y: transform.translation.y,
+                        x: transform.translation.x
+                    });
+                    sound.send(SoundEvent::sound(SoundType::PENALTY).at(transform.translation.truncate()));
+                }
+            }
+            else if successfull_hit {
                 if !fruit.sliced {
-                    session.score += 1;
+                    session.add_score(event.player, 1);
                     start_slice_animation(&mut commands, &entity);
                     fruit.slice();
-                    hitted_fruits.push(fruit.clone());
+                    hitted_fruits.push((fruit.clone(), transform.translation.truncate()));
 
 
                     if fruit.fruit_type == FruitType::PINEAPPLE {
@@ -153,8 +211,8 @@ pub fn hit(
                         });
                     } 
 
-                    else if fruit.fruit_type == FruitType::POME && session.lives_left < 5 {
-                        session.lives_left += 1;
+                    else if fruit.fruit_type == FruitType::POME && *session.lives_mut(side) < MAX_LIVES {
+                        *session.lives_mut(side) += 1;
 
                         text.send(TextEvent{
                             text: "+1 live!".to_string(), 
@@ -173,16 +231,21 @@ pub fn hit(
             }
         };
 
-        sound.send(SoundEvent::sound(SoundType::SLASH));
+        let chef_position = event.translation.truncate();
+        sound.send(SoundEvent::sound(SoundType::SLASH).at(chef_position));
 
         if !hitted_fruits.is_empty() {
-            sound.send(SoundEvent::sound(SoundType::HIT));
-            
+            sound.send(SoundEvent::sound(SoundType::HIT).at(chef_position));
+            slice.send(SliceEvent {
+                fruits: hitted_fruits.iter().map(|(fruit, _)| fruit.fruit_type.clone()).collect(),
+                boost: false,
+                player: event.player
+            });
         }
 
         // send sound
-        for fruit in hitted_fruits {
-            sound.send(SoundEvent::fruit_sound(fruit.fruit_type))
+        for (fruit, position) in hitted_fruits {
+            sound.send(SoundEvent::fruit_sound(fruit.fruit_type).at(position));
         }
     }
 }
@@ -206,18 +269,19 @@ pub fn fall(
 
 
 pub fn despawn_fallen_fruits(
-    query: Query<(&Transform, &Fruit, Entity)>, 
+    query: Query<(&Transform, &Fruit, Entity, Sided)>, 
     mut commands: Commands,
     mut session: ResMut<Session>,
     mut wave: EventWriter<WaveEvent>,
     mut sound: EventWriter<SoundEvent>
 ) {
-    for (transform, fruit, entity) in &query {
+    for (transform, fruit, entity, (side, garbage)) in &query {
         if transform.translation.y <= DESPAWN_FLOOR {
-            if !fruit.sliced {
-                session.lives_left -= 1;
+            if !fruit.sliced && garbage != Some(&Garbage::Bomb) {
+                let lives = session.lives_mut(side.map(|side| side.0));
+                *lives = lives.saturating_sub(1);
                 wave.send(WaveEvent(transform.translation.x));
-                sound.send(SoundEvent::sound(SoundType::PENALTY));
+                sound.send(SoundEvent::sound(SoundType::PENALTY).at(transform.translation.truncate()));
             }
             commands.entity(entity).despawn();
         }
@@ -226,26 +290,5 @@ pub fn despawn_fallen_fruits(
 
 
 pub fn start_slice_animation(commands: &mut Commands, entity: &Entity) {
-    commands.entity(*entity).insert(HitAnimation).insert(Clock::millis(SLICE_ANIMATION_SPEED))
-    ;
-}
-
-
-pub fn animate_slice(
-    time: Res<Time>,
-    mut query: Query<(&mut TextureAtlasSprite, &mut Clock, Entity), With<HitAnimation>>,
-    mut commands: Commands
-) {
-
-    for (mut sprite, mut clock, entity) in query.iter_mut() {
-        clock.tick(time.delta());
-        if clock.just_finished() {
-            if sprite.index == 7 { 
-                commands.entity(entity).remove::<HitAnimation>();
-            }
-            else {
-                sprite.index += 1;
-            }
-        }        
-    }
+    commands.entity(*entity).insert(SpriteAnimation::new([SLICE_CLIP]));
 }
@@ -7,6 +7,9 @@ pub mod splash;
 pub mod boost;
 pub mod penalty;
 pub mod text;
+pub mod pool;
+pub mod stain;
+pub mod versus;
 
 
 pub struct FruitPlugin;
@@ -20,7 +23,9 @@ impl Plugin for FruitPlugin {
                 boost::BoostPlugin,
                 fruit::FruitPlugin,
                 penalty::PenaltyPlugin,
-                text::TextPlugin
+                text::TextPlugin,
+                stain::StainPlugin,
+                versus::VersusPlugin
             ))
         ;
     }
@@ -1,8 +1,10 @@
 use bevy::prelude::*;
 
-use crate::{global::AppState, components::Clock};
+use crate::animation::{AnimationFinished, Clip, OnFinished, SpriteAnimation};
+use crate::global::playing;
 
-use super::sprite::create_wave;
+use super::pool::{acquire, release, Active, Idle};
+use super::sprite::{create_wave, EffectAtlases};
 
 
 pub struct PenaltyPlugin;
@@ -11,13 +13,16 @@ pub struct PenaltyPlugin;
 impl Plugin for PenaltyPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(Update, (spawn_wave, animate_wave).run_if(in_state(AppState::InGame)))
+            .add_systems(Update, (spawn_wave, release_wave).run_if(playing))
             .add_event::<WaveEvent>()
         ;
     }
 }
 
 
+const WAVE_CLIP: Clip = Clip::new("wave", 0, 5, &[70]).then(OnFinished::Event);
+
+
 #[derive(Component)]
 pub struct Wave;
 
@@ -25,37 +30,32 @@ pub struct Wave;
 pub struct WaveEvent(pub f32);
 
 
-fn spawn_wave(
-    asset_server: Res<AssetServer>,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+pub fn spawn_wave(
+    atlases: Res<EffectAtlases>,
     mut commands: Commands,
     mut events: EventReader<WaveEvent>,
+    idle: Query<Entity, (With<Wave>, With<Idle>)>
 ) {
+    let mut idle = idle.iter();
+
     for event in events.iter() {
-        commands.spawn((
+        acquire(&mut commands, &mut idle, (
             Wave, 
-            create_wave(&asset_server, &mut texture_atlases, event.0),
-            Clock::millis(70)
+            create_wave(&atlases, event.0),
+            SpriteAnimation::new([WAVE_CLIP])
         ));
     }
 }
 
 
-fn animate_wave(
-    time: Res<Time>,
-    mut query: Query<(&mut TextureAtlasSprite, &mut Clock, Entity), With<Wave>>,
-    mut commands: Commands
+pub fn release_wave(
+    mut commands: Commands,
+    mut events: EventReader<AnimationFinished>,
+    query: Query<(), Active<Wave>>
 ) {
-    for (mut sprite, mut clock, entity) in query.iter_mut() {
-        clock.tick(time.delta());
-
-        if clock.finished() {
-            if sprite.index == 5 {
-                commands.entity(entity).despawn();
-            }else {
-                sprite.index += 1;
-            }
+    for event in &mut events {
+        if query.contains(event.entity) {
+            release(&mut commands, event.entity);
         }
-
     }
 }
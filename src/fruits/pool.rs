@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+
+
+/// Marks a finished effect entity that waits hidden to be reused.
+#[derive(Component)]
+pub struct Idle;
+
+
+/// Filter for effects that are currently playing.
+pub type Active<T> = (With<T>, Without<Idle>);
+
+
+/// Reuses the next idle entity for the effect or spawns a new one when the pool is empty.
+pub fn acquire<B: Bundle>(commands: &mut Commands, idle: &mut impl Iterator<Item = Entity>, bundle: B) {
+    match idle.next() {
+        Some(entity) => {
+            commands.entity(entity).remove::<Idle>().insert(bundle);
+        }
+        None => {
+            commands.spawn(bundle);
+        }
+    }
+}
+
+
+/// Hides the effect and returns it to the pool instead of despawning it.
+pub fn release(commands: &mut Commands, entity: Entity) {
+    commands.entity(entity).insert((Idle, Visibility::Hidden));
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+
+    use crate::animation::SpriteAnimationPlugin;
+    use crate::particles::ParticlesPlugin;
+    use crate::global::AppState;
+    use crate::fruits::boost::{release_boost_shot, spawn_boost_shot, BoostEvent, BoostShot};
+    use crate::fruits::fruit::FruitType;
+    use crate::fruits::penalty::{release_wave, spawn_wave, Wave, WaveEvent};
+    use crate::fruits::splash::{animate_splash, spawn_splash, Splash, SplashEvent};
+    use crate::fruits::sprite::EffectAtlases;
+
+    const FRAME: Duration = Duration::from_millis(50);
+
+    fn play_effects(mut splash: EventWriter<SplashEvent>, mut wave: EventWriter<WaveEvent>, mut boost: EventWriter<BoostEvent>) {
+        splash.send(SplashEvent { x: 0., y: 200., fruit_type: FruitType::APPLE });
+        wave.send(WaveEvent(100.));
+        boost.send(BoostEvent { point1: Vec3::new(100., 100., 0.), point2: Vec3::new(0., -380., 10.) });
+    }
+
+    fn effects_app() -> App {
+        let mut app = App::new();
+
+        app
+            .add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .add_asset::<Image>()
+            .add_asset::<TextureAtlas>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+            .add_event::<SplashEvent>()
+            .add_event::<WaveEvent>()
+            .add_event::<BoostEvent>()
+            .add_systems(Update, (
+                play_effects,
+                spawn_splash,
+                animate_splash,
+                spawn_wave,
+                release_wave,
+                spawn_boost_shot,
+                release_boost_shot
+            ).chain())
+            .add_plugins((SpriteAnimationPlugin, ParticlesPlugin))
+            .add_state::<AppState>();
+
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let atlases = EffectAtlases::new(&asset_server, &mut app.world.resource_mut::<Assets<TextureAtlas>>());
+        app.insert_resource(atlases);
+
+        app
+    }
+
+    fn effect_entities(app: &mut App) -> usize {
+        let mut effects = app.world.query_filtered::<Entity, Or<(With<Splash>, With<Wave>, With<BoostShot>)>>();
+        effects.iter(&app.world).count()
+    }
+
+    fn run_for(app: &mut App, seconds: u64) {
+        for _ in 0..Duration::from_secs(seconds).as_millis() / FRAME.as_millis() {
+            app.update();
+        }
+    }
+
+    /// Simulates a 30 minute session with every effect playing each frame.
+    #[test]
+    fn test_it_keeps_assets_flat_over_a_long_session() {
+        let mut app = effects_app();
+
+        run_for(&mut app, 60);
+        let atlases = app.world.resource::<Assets<TextureAtlas>>().len();
+        let effects = effect_entities(&mut app);
+        let entities = app.world.entities().len();
+
+        for _ in 1..30 {
+            run_for(&mut app, 60);
+
+            // particles come and go at random, they only must not pile up
+            assert!(app.world.entities().len() <= entities * 5 / 4);
+        }
+
+        assert_eq!(atlases, 3);
+        assert_eq!(app.world.resource::<Assets<TextureAtlas>>().len(), atlases);
+        assert_eq!(effect_entities(&mut app), effects);
+    }
+}
@@ -1,32 +1,49 @@
 use std::time::Duration;
 
+#[cfg(feature = "dev")]
+use bevy::ecs::system::SystemState;
 use bevy::prelude::*;
 
 use super::fruit::{Fruit, FruitType};
 use super::sprite::{create_pineapple, create_pome, create_sprite, FruitTextures};
+use super::versus::{Garbage, Side};
 
-use crate::utils::random::{randint, probably};
+use crate::utils::random::GameRng;
 use crate::level::LevelUpdate;
-use crate::global::AppState;
+use crate::global::{in_session, GameplaySet, VIRTUAL_WIDTH, SPAWN_Y};
+use crate::states::session::{GameMode, MAX_PLAYERS};
+#[cfg(feature = "dev")]
+use crate::console::{arg, ConsoleExt};
 
 const MAX_COMBO_FRUITS: i32 = 3;
-const FRUITS_SPAWN_BORDERS: (i32, i32) = (-350, 350);
+pub const FRUITS_SPAWN_BORDERS: (i32, i32) = (-SPAWN_HALF_WIDTH, SPAWN_HALF_WIDTH);
+const SPAWN_HALF_WIDTH: i32 = (VIRTUAL_WIDTH / 2.) as i32 - 220;
 
 const SPAWN_INTENSITY_UPDATE_PERCENT: u32 = 95;
+const FASTEST_SPAWN_TIMER: Duration = Duration::from_millis(450);
 const INITIAL_SPAWN_TIMER: Duration = Duration::from_millis(800);
 
 const PINEAPPLE_SPAWN_PROPABILITY: f64 = 0.04;
 const POME_SPAWN_PROPABILITY: f64 = 0.03;
 
+/// Gap between the fruits of the rain pattern.
+#[cfg(feature = "dev")]
+const RAIN_GAP: usize = 120;
+
 
 pub struct SpawnPlugin;
 
 
 impl Plugin for SpawnPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update, (spawn_fruits, increase_spawn_intensity).run_if(in_state(AppState::InGame))
-        );
+        app
+            .insert_resource(GameRng::from_time())
+            .add_systems(Update, (spawn_fruits, increase_spawn_intensity).chain().in_set(GameplaySet::Spawn).run_if(in_session));
+
+        #[cfg(feature = "dev")]
+        app
+            .add_console_command("spawn", "spawn <fruit|pineapple|pome> [x] or spawn pattern <rain>", spawn_command)
+            .add_console_command("seed", "seed [n]", seed_command);
     }
 }
 
@@ -36,8 +53,19 @@ impl Plugin for SpawnPlugin {
 pub struct SpawnTimer(pub Timer);
 
 impl SpawnTimer {
-    pub fn new() -> Self {
-        Self(Timer::new(INITIAL_SPAWN_TIMER, TimerMode::Repeating))
+    /// Timer of a run starting at `level`, sped up once for every level before it.
+    pub fn at_level(level: u32) -> Self {
+        let duration = (1..level).fold(INITIAL_SPAWN_TIMER, |duration, _| faster(duration));
+        Self(Timer::new(duration, TimerMode::Repeating))
+    }
+}
+
+
+fn faster(duration: Duration) -> Duration {
+    if duration > FASTEST_SPAWN_TIMER {
+        duration / 100 * SPAWN_INTENSITY_UPDATE_PERCENT
+    } else {
+        duration
     }
 }
 
@@ -48,13 +76,25 @@ enum FoodType {
     POME
 }
 
+#[cfg(feature = "dev")]
+impl FoodType {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "fruit" => Some(Self::FRUIT),
+            "pineapple" => Some(Self::PINEAPPLE),
+            "pome" => Some(Self::POME),
+            _ => None
+        }
+    }
+}
+
 
-fn random_fruit_type() -> FoodType {
-    if probably(PINEAPPLE_SPAWN_PROPABILITY) {
+fn random_fruit_type(rng: &mut GameRng, pineapples: bool) -> FoodType {
+    if pineapples && rng.probably(PINEAPPLE_SPAWN_PROPABILITY) {
         return FoodType::PINEAPPLE;
     }
 
-    if probably(POME_SPAWN_PROPABILITY) {
+    if rng.probably(POME_SPAWN_PROPABILITY) {
         return FoodType::POME;
     }
     
@@ -67,63 +107,153 @@ pub fn spawn_fruits(
     mut spawn_timer: ResMut<SpawnTimer>,
     time: Res<Time>,
     fruit_assets: Res<FruitTextures>,
+    mode: Res<GameMode>,
+    mut rng: ResMut<GameRng>
 ) {
     spawn_timer.0.tick(time.delta());
 
     if spawn_timer.0.finished() {
-        let x = randint(FRUITS_SPAWN_BORDERS.0, FRUITS_SPAWN_BORDERS.1) as f32;
+        if *mode == GameMode::Versus {
+            // both sides get their own fruits at the same pace, boosts are off in versus
+            for side in (0..MAX_PLAYERS).map(Side) {
+                let x = side.random_x(&mut rng);
+                let food_type = random_fruit_type(&mut rng, false);
 
-        let fruit_type = random_fruit_type();
-
-        match fruit_type {
-            FoodType::PINEAPPLE => {
-                let sprite = create_pineapple(&fruit_assets, x, 350.);
-                
-                let mut fruit = Fruit::new();
-                fruit.fruit_type = FruitType::PINEAPPLE;
-                fruit.rotation_speed = 0.0;
-                
-                commands.spawn((sprite, fruit));
-            }
-            FoodType::POME => {
-                let sprite: SpriteSheetBundle = create_pome(&fruit_assets, x, 350.);
-                
-                let mut fruit = Fruit::new();
-                fruit.fruit_type = FruitType::POME;
-                
-                commands.spawn((sprite, fruit));
-            }
-
-            FoodType::FRUIT => {
-                let combo = randint(1, MAX_COMBO_FRUITS+1);
-                for i in 0..combo {
-                    
-                    let (sprite, fruit_type) = create_sprite(
-                        &fruit_assets, 
-                        x, 
-                        i as f32 * 30. + 330.,
-                        5. + (MAX_COMBO_FRUITS - i) as f32
-                    );
-
-                    let mut fruit = Fruit::new();
-                    fruit.fruit_type = fruit_type.clone();
-                    commands.spawn((sprite, fruit));
+                for entity in spawn_food(&mut commands, &fruit_assets, &mut rng, x, &food_type) {
+                    commands.entity(entity).insert(side);
                 }
             }
         }
+        else {
+            let x = rng.randint(FRUITS_SPAWN_BORDERS.0, FRUITS_SPAWN_BORDERS.1) as f32;
+            let food_type = random_fruit_type(&mut rng, true);
+            spawn_food(&mut commands, &fruit_assets, &mut rng, x, &food_type);
+        }
     }
 }
 
 
+fn spawn_food(commands: &mut Commands, fruit_assets: &Res<FruitTextures>, rng: &mut GameRng, x: f32, food_type: &FoodType) -> Vec<Entity> {
+    match food_type {
+        FoodType::PINEAPPLE => {
+            let sprite = create_pineapple(fruit_assets, x, SPAWN_Y);
+            
+            let mut fruit = Fruit::new();
+            fruit.fruit_type = FruitType::PINEAPPLE;
+            fruit.rotation_speed = 0.0;
+            
+            vec![commands.spawn((sprite, fruit)).id()]
+        }
+        FoodType::POME => {
+            let sprite: SpriteSheetBundle = create_pome(fruit_assets, x, SPAWN_Y);
+            
+            let mut fruit = Fruit::new();
+            fruit.fruit_type = FruitType::POME;
+            
+            vec![commands.spawn((sprite, fruit)).id()]
+        }
+
+        FoodType::FRUIT => {
+            let combo = rng.randint(1, MAX_COMBO_FRUITS+1);
+
+            (0..combo).map(|i| {
+                let (sprite, fruit_type) = create_sprite(
+                    fruit_assets, 
+                    x, 
+                    i as f32 * 30. + SPAWN_Y - 20.,
+                    5. + (MAX_COMBO_FRUITS - i) as f32
+                );
+
+                let mut fruit = Fruit::new();
+                fruit.fruit_type = fruit_type;
+                commands.spawn((sprite, fruit)).id()
+            }).collect()
+        }
+    }
+}
+
+
+/// Drops a garbage fruit somewhere on `side`.
+pub fn spawn_garbage(commands: &mut Commands, fruit_assets: &Res<FruitTextures>, rng: &mut GameRng, side: Side, garbage: Garbage) {
+    let x = side.random_x(rng);
+    let (mut sprite, fruit_type) = create_sprite(fruit_assets, x, SPAWN_Y + rng.randint(0, 80) as f32, 8.);
+    sprite.sprite.color = garbage.tint();
+
+    let mut fruit = Fruit::new();
+    fruit.fruit_type = fruit_type;
+
+    commands.spawn((sprite, fruit, side, garbage));
+}
+
+
 pub fn increase_spawn_intensity(
-    events: EventReader<LevelUpdate>,
+    mut events: EventReader<LevelUpdate>,
     mut spawn_timer: ResMut<SpawnTimer>
 ) {
-    if !events.is_empty() {
-        let duration = spawn_timer.0.duration();
+    for _ in &mut events {
+        let duration = faster(spawn_timer.0.duration());
+        spawn_timer.0.set_duration(duration);
+    }
+}
+
+
+#[cfg(feature = "dev")]
+fn spawn_command(world: &mut World, args: &[&str]) -> Result<String, String> {
+    if !world.contains_resource::<FruitTextures>() {
+        return Err("fruits are still loading".to_string());
+    }
+
+    let mut state = SystemState::<(Commands, Res<FruitTextures>, ResMut<GameRng>)>::new(world);
+    let (mut commands, fruit_assets, mut rng) = state.get_mut(world);
+
+    let spawned = match args.first().copied() {
+        Some("pattern") => match args.get(1).copied() {
+            Some("rain") => spawn_rain(&mut commands, &fruit_assets),
+            _ => return Err("unknown pattern".to_string())
+        },
+        Some(name) => {
+            let food_type = FoodType::from_name(name).ok_or_else(|| format!("unknown food {name}"))?;
+            let x = if args.len() > 1 {
+                arg(args, 1)?
+            } else {
+                rng.randint(FRUITS_SPAWN_BORDERS.0, FRUITS_SPAWN_BORDERS.1) as f32
+            };
 
-        if duration.as_millis() > 450 {
-            spawn_timer.0.set_duration(duration / 100 * SPAWN_INTENSITY_UPDATE_PERCENT);  
+            spawn_food(&mut commands, &fruit_assets, &mut rng, x, &food_type).len()
         }
+        None => return Err("missing argument".to_string())
+    };
+
+    state.apply(world);
+    Ok(format!("spawned {spawned} fruits"))
+}
+
+
+/// A row of fruits across the whole spawn zone, every other one a bit higher.
+#[cfg(feature = "dev")]
+fn spawn_rain(commands: &mut Commands, fruit_assets: &Res<FruitTextures>) -> usize {
+    (FRUITS_SPAWN_BORDERS.0..=FRUITS_SPAWN_BORDERS.1)
+        .step_by(RAIN_GAP)
+        .enumerate()
+        .map(|(i, x)| {
+            let (sprite, fruit_type) = create_sprite(fruit_assets, x as f32, SPAWN_Y + (i % 2) as f32 * 60., 6.);
+
+            let mut fruit = Fruit::new();
+            fruit.fruit_type = fruit_type;
+            commands.spawn((sprite, fruit));
+        })
+        .count()
+}
+
+
+/// Prints the seed, or reseeds the fruits of the rest of the run.
+#[cfg(feature = "dev")]
+fn seed_command(world: &mut World, args: &[&str]) -> Result<String, String> {
+    if args.is_empty() {
+        return Ok(format!("seed {}", world.resource::<GameRng>().seed()));
     }
+
+    let seed: u64 = arg(args, 0)?;
+    world.insert_resource(GameRng::new(seed));
+    Ok(format!("seed {seed}"))
 }
@@ -1,10 +1,12 @@
 use bevy::prelude::*;
 
-use crate::components::Clock;
-use crate::global::AppState;
+use crate::animation::{Clip, OnFinished, SpriteAnimation};
+use crate::global::playing;
+use crate::particles::{spawn_emitter, EmitterSpec};
 
-use super::fruit::FruitType;
-use super::sprite::{create_splash, SplashColor};
+use super::fruit::{FruitType, DESPAWN_FLOOR};
+use super::pool::{acquire, release, Active, Idle};
+use super::sprite::{create_splash, EffectAtlases, SplashColor};
 
 
 
@@ -13,7 +15,7 @@ pub struct SplashPlugin;
 impl Plugin for SplashPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(Update, (spawn_splash, animate_splash).run_if(in_state(AppState::InGame)))
+            .add_systems(Update, (spawn_splash, animate_splash).run_if(playing))
             .add_event::<SplashEvent>()
         ;
     }
@@ -21,7 +23,29 @@ impl Plugin for SplashPlugin {
 
 
 
-const SPLASH_ANIMATION_SPEED: u64 = 80;
+const SPLASH_CLIP: Clip = Clip::new("splash", 0, 5, &[80]).then(OnFinished::Remove);
+
+/// Juice droplets thrown up from a sliced fruit, tinted by its `SplashColor`.
+const JUICE: EmitterSpec = EmitterSpec {
+    burst: 14,
+    lifetime: (0.4, 0.8),
+    speed: (150., 380.),
+    spread: 1.2,
+    gravity: -1100.,
+    size: (9., 3.),
+    ..EmitterSpec::DEFAULT
+};
+
+const SEEDS: EmitterSpec = EmitterSpec {
+    burst: 8,
+    lifetime: (0.5, 0.9),
+    speed: (120., 300.),
+    spread: 1.,
+    gravity: -1200.,
+    size: (6., 5.),
+    ..EmitterSpec::DEFAULT
+};
+
 
 #[derive(Component)]
 pub struct Splash;
@@ -37,48 +61,47 @@ pub struct SplashEvent {
 pub fn spawn_splash(
     mut commands: Commands,
     mut event_reader: EventReader<SplashEvent>,
-    asset_server: Res<AssetServer>,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>, 
+    atlases: Res<EffectAtlases>,
+    idle: Query<Entity, (With<Splash>, With<Idle>)>
 ){
+    let mut idle = idle.iter();
+
     for event in event_reader.iter() {
-        let splash_color = match event.fruit_type {
-            FruitType::APPLE | FruitType::BANANA | FruitType::PINEAPPLE => SplashColor::Yellow,
-            FruitType::ORANGE => SplashColor::Orange,
-            FruitType::STRAWBERRY | FruitType::WATERMELON => SplashColor::Red,
-            FruitType::POME => SplashColor::Orange
+        let splash_color = SplashColor::of(&event.fruit_type);
+
+        let position = Vec3::new(event.x, event.y, 3.);
+        let juice = splash_color.color();
+        spawn_emitter(&mut commands, EmitterSpec { color: (juice, juice.with_a(0.)), ..JUICE }, position);
+
+        let seed = match event.fruit_type {
+            FruitType::WATERMELON => Some(Color::rgb_u8(35, 25, 20)),
+            FruitType::STRAWBERRY => Some(Color::rgb_u8(255, 236, 140)),
+            _ => None
         };
+        if let Some(seed) = seed {
+            spawn_emitter(&mut commands, EmitterSpec { color: (seed, seed.with_a(0.5)), ..SEEDS }, position);
+        }
 
-        let sprite = create_splash(
-            &asset_server, 
-            &mut texture_atlases, 
-            event.x, 
-            event.y,
-            splash_color
-        );
+        let sprite = create_splash(&atlases, event.x, event.y, splash_color);
         
-        commands.spawn((Splash, Clock::millis(SPLASH_ANIMATION_SPEED), sprite));        
+        acquire(&mut commands, &mut idle, (Splash, SpriteAnimation::new([SPLASH_CLIP]), sprite));
     }
 }
 
 
 pub fn animate_splash(
     time: Res<Time>,
-    mut query: Query<(&mut TextureAtlasSprite, &mut Transform, &mut Clock, Entity), With<Splash>>,
+    mut query: Query<(&mut TextureAtlasSprite, &mut Transform, Entity), Active<Splash>>,
     mut commands: Commands
 ) {
-    for (mut sprite, mut transform, mut clock, entity) in query.iter_mut() {
-        clock.tick(time.delta());
-        if clock.just_finished() && sprite.index < 5 { 
-            sprite.index += 1;
-        }        
-
+    for (mut sprite, mut transform, entity) in query.iter_mut() {
         transform.translation.y -= 300. * time.delta_seconds();
         
         let alpha = sprite.color.a();
         sprite.color.set_a(alpha - 0.01);
 
-        if transform.translation.y < -480. {
-            commands.entity(entity).despawn();
+        if transform.translation.y < DESPAWN_FLOOR {
+            release(&mut commands, entity);
         }
     }
 }
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use crate::utils::random::{randint, probably};
+use crate::global::FLOOR_Y;
 use super::fruit::FruitType;
 
 
@@ -99,6 +100,30 @@ pub fn create_pome(fruit_assets: &Res<FruitTextures>, x: f32, y: f32) -> SpriteS
 
 
 
+/// Atlases shared by every splash, wave and boost shot, created once after loading.
+#[derive(Resource)]
+pub struct EffectAtlases {
+    splash: Handle<TextureAtlas>,
+    wave: Handle<TextureAtlas>,
+    boost_shot: Handle<TextureAtlas>
+}
+
+impl EffectAtlases {
+    pub fn new(asset_server: &AssetServer, texture_atlases: &mut Assets<TextureAtlas>) -> Self {
+        let mut atlas = |image: &str, tile: Vec2| {
+            texture_atlases.add(TextureAtlas::from_grid(asset_server.load(image), tile, 6, 1, None, None))
+        };
+
+        Self {
+            splash: atlas("images/splash.png", Vec2::new(50., 50.)),
+            wave: atlas("images/wave.png", Vec2::new(60., 80.)),
+            boost_shot: atlas("images/boost_shot.png", Vec2::new(100., 10.))
+        }
+    }
+}
+
+
+
 pub enum SplashColor {
     Red,
     Orange,
@@ -106,7 +131,15 @@ pub enum SplashColor {
 }
 
 impl SplashColor {
-    fn color(&self) -> Color {
+    pub const fn of(fruit_type: &FruitType) -> Self {
+        match fruit_type {
+            FruitType::APPLE | FruitType::BANANA | FruitType::PINEAPPLE => Self::Yellow,
+            FruitType::ORANGE | FruitType::POME => Self::Orange,
+            FruitType::STRAWBERRY | FruitType::WATERMELON => Self::Red
+        }
+    }
+
+    pub fn color(&self) -> Color {
         match self {
             Self::Orange => Color::rgba_u8(245, 155, 66, 200),
             Self::Yellow => Color::rgba_u8(255, 230, 41, 200),
@@ -116,94 +149,66 @@ impl SplashColor {
 }
 
 
-pub fn create_splash(
-    asset_server: &Res<AssetServer>, 
-    texture_atlases: &mut ResMut<Assets<TextureAtlas>>, 
-    x: f32, 
-    y: f32,
-    color: SplashColor
-) -> SpriteSheetBundle {
+pub fn create_splash(atlases: &EffectAtlases, x: f32, y: f32, color: SplashColor) -> SpriteSheetBundle {
     let transform = Transform::
         from_xyz(x, y - 30., 1.)
         .with_scale(Vec3::splat(4.))
         .with_rotation(Quat::from_rotation_z(randint(0, 360) as f32))
     ;
 
-    let texture = TextureAtlas::from_grid(
-        asset_server.load("images/splash.png"),
-        Vec2::new(50., 50.),
-        6,
-        1,
-        None,
-        None
-    );
-
-    let texture_atlas = texture_atlases.add(texture);
-
     SpriteSheetBundle { 
         sprite: TextureAtlasSprite {
             color: color.color(),
             ..default()
         },
-        texture_atlas, 
+        texture_atlas: atlases.splash.clone(), 
         transform, 
         ..default()
     }
 }
 
 
-pub fn create_wave(
-    asset_server: &Res<AssetServer>, 
-    texture_atlases: &mut ResMut<Assets<TextureAtlas>>, 
-    x: f32 
-) -> SpriteSheetBundle {
-    let transform = Transform::from_xyz(x, -220., 1.).with_scale(Vec3::splat(4.));
+/// Last splash frame, drawn once into the stain layer.
+pub fn create_stain(atlases: &EffectAtlases, x: f32, y: f32, color: SplashColor) -> SpriteSheetBundle {
+    let transform = Transform::
+        from_xyz(x, y, 0.)
+        .with_scale(Vec3::splat(randint(3, 6) as f32))
+        .with_rotation(Quat::from_rotation_z(randint(0, 360) as f32))
+    ;
+
+    SpriteSheetBundle { 
+        sprite: TextureAtlasSprite {
+            index: 5,
+            color: color.color().with_a(0.3),
+            ..default()
+        },
+        texture_atlas: atlases.splash.clone(), 
+        transform, 
+        ..default()
+    }
+}
 
-    let texture = TextureAtlas::from_grid(
-        asset_server.load("images/wave.png"),
-        Vec2::new(60., 80.),
-        6,
-        1,
-        None,
-        None
-    );
 
-    let texture_atlas = texture_atlases.add(texture);
+pub fn create_wave(atlases: &EffectAtlases, x: f32) -> SpriteSheetBundle {
+    let transform = Transform::from_xyz(x, FLOOR_Y, 1.).with_scale(Vec3::splat(4.));
 
     SpriteSheetBundle { 
-        texture_atlas, 
+        texture_atlas: atlases.wave.clone(), 
         transform, 
         ..default()
     }
 }
 
 
-pub fn create_boost_shot(
-    asset_server: &Res<AssetServer>, 
-    texture_atlases: &mut ResMut<Assets<TextureAtlas>>, 
-    x: f32,
-    y: f32,
-    angle: f32
-) -> SpriteSheetBundle {
+pub fn create_boost_shot(atlases: &EffectAtlases, x: f32, y: f32, angle: f32) -> SpriteSheetBundle {
     let transform = Transform::
         from_xyz(x, y, 1.)
         .with_scale(Vec3::splat(5.))
         .with_rotation(Quat::from_rotation_z(angle))
     ;
 
-    let texture = TextureAtlas::from_grid(
-        asset_server.load("images/boost_shot.png"),
-        Vec2::new(100., 10.),
-        6,
-        1,
-        None,
-        None
-    );
-
-    let texture_atlas = texture_atlases.add(texture);
-
     SpriteSheetBundle { 
-        texture_atlas, 
+        texture_atlas: atlases.boost_shot.clone(), 
         transform, 
         ..default()
     }
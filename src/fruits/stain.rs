@@ -0,0 +1,164 @@
+use bevy::core_pipeline::clear_color::ClearColorConfig;
+use bevy::prelude::*;
+use bevy::render::camera::{RenderTarget, ScalingMode};
+use bevy::render::render_resource::{Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages};
+use bevy::render::view::RenderLayers;
+
+use crate::global::{playing, AppState, VIRTUAL_HEIGHT, VIRTUAL_WIDTH};
+use crate::settings::{Settings, Toggle};
+
+use super::splash::SplashEvent;
+use super::sprite::{create_stain, EffectAtlases, SplashColor};
+
+
+const FROM_MENU_ENTER: OnTransition<AppState> = OnTransition::<AppState> {
+    from: AppState::MainMenu,
+    to: AppState::InGame
+};
+
+const FROM_FINISH_ENTER: OnTransition<AppState> = OnTransition::<AppState> {
+    from: AppState::Finish,
+    to: AppState::InGame
+};
+
+/// Stains are only drawn by the stain camera.
+const STAIN_LAYER: u8 = 1;
+/// The stain texture has half the playfield resolution.
+const STAIN_TEXTURE_SCALE: f32 = 0.5;
+
+
+pub struct StainPlugin;
+
+impl Plugin for StainPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Startup, setup)
+            .add_systems(FROM_MENU_ENTER, clear_stains)
+            .add_systems(FROM_FINISH_ENTER, clear_stains)
+            .add_systems(Update, (
+                // redrawing a stain would darken it, despawn in every state
+                despawn_drawn_stains,
+                spawn_stains.after(despawn_drawn_stains).run_if(playing),
+                finish_clear
+            ))
+        ;
+    }
+}
+
+
+/// Renders stains into the stain texture, never clears it between frames.
+#[derive(Component)]
+struct StainCamera {
+    clear: bool
+}
+
+/// Stain that has to be drawn into the texture once.
+#[derive(Component)]
+struct Stain;
+
+
+fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let size = Extent3d {
+        width: (VIRTUAL_WIDTH * STAIN_TEXTURE_SCALE) as u32,
+        height: (VIRTUAL_HEIGHT * STAIN_TEXTURE_SCALE) as u32,
+        depth_or_array_layers: 1
+    };
+
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("stains"),
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[]
+        },
+        ..default()
+    };
+    // transparent until the first stain lands
+    image.resize(size);
+
+    let image = images.add(image);
+
+    let mut camera = Camera2dBundle {
+        camera_2d: Camera2d { clear_color: ClearColorConfig::None },
+        camera: Camera {
+            target: RenderTarget::Image(image.clone()),
+            order: -1,
+            ..default()
+        },
+        ..default()
+    };
+    camera.projection.scaling_mode = ScalingMode::Fixed {
+        width: VIRTUAL_WIDTH,
+        height: VIRTUAL_HEIGHT
+    };
+
+    commands.spawn((
+        camera,
+        StainCamera { clear: false },
+        RenderLayers::layer(STAIN_LAYER),
+        UiCameraConfig { show_ui: false }
+    ));
+
+    // just above the background from `main::setup`
+    commands.spawn(SpriteBundle {
+        texture: image,
+        sprite: Sprite {
+            custom_size: Some(Vec2::new(VIRTUAL_WIDTH, VIRTUAL_HEIGHT)),
+            ..default()
+        },
+        transform: Transform::from_xyz(0., 0., -0.5),
+        ..default()
+    });
+}
+
+
+fn spawn_stains(
+    mut commands: Commands,
+    mut events: EventReader<SplashEvent>,
+    settings: Res<Settings>,
+    atlases: Res<EffectAtlases>
+) {
+    if settings.is_on(Toggle::LowEffects) {
+        events.clear();
+        return;
+    }
+
+    for event in &mut events {
+        let stain = create_stain(&atlases, event.x, event.y, SplashColor::of(&event.fruit_type));
+        commands.spawn((stain, Stain, RenderLayers::layer(STAIN_LAYER)));
+    }
+}
+
+
+/// Stains stay in the texture, the entities are only needed for one frame.
+fn despawn_drawn_stains(mut commands: Commands, query: Query<Entity, With<Stain>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+
+fn clear_stains(mut cameras: Query<&mut StainCamera>) {
+    for mut camera in &mut cameras {
+        camera.clear = true;
+    }
+}
+
+
+/// Clears the texture for exactly one frame after a restart.
+fn finish_clear(mut cameras: Query<(&mut Camera2d, &mut StainCamera)>) {
+    for (mut camera_2d, mut camera) in &mut cameras {
+        if matches!(camera_2d.clear_color, ClearColorConfig::Custom(_)) {
+            camera_2d.clear_color = ClearColorConfig::None;
+        }
+
+        if camera.clear {
+            camera.clear = false;
+            camera_2d.clear_color = ClearColorConfig::Custom(Color::NONE);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use bevy::prelude::*;
+
+use crate::global::{AppState, GameplaySet, SIDE_GAP, VIRTUAL_HEIGHT, VIRTUAL_WIDTH};
+use crate::sound::{SoundEvent, SoundType};
+use crate::states::session::{GameMode, MAX_PLAYERS};
+use crate::utils::random::GameRng;
+
+use super::fruit::SliceEvent;
+use super::spawn::spawn_garbage;
+use super::sprite::FruitTextures;
+use super::text::TextEvent;
+
+
+pub struct VersusPlugin;
+
+impl Plugin for VersusPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Startup, setup)
+            .add_systems(Update, (
+                show_divider.run_if(resource_changed::<GameMode>()),
+                send_garbage
+                    .in_set(GameplaySet::Garbage)
+                    .run_if(in_state(AppState::InGame).and_then(resource_equals(GameMode::Versus)))
+            ))
+        ;
+    }
+}
+
+
+/// Fruits spawn at least this far from the middle line so they can't be sliced from the other side.
+const SIDE_MARGIN: i32 = 110;
+/// Outer edge of each side's spawn zone, 120 from the screen edge.
+const SIDE_HALF_WIDTH: i32 = (VIRTUAL_WIDTH / 2.) as i32 - 120;
+pub const SIDE_SPAWN_BORDERS: [(i32, i32); MAX_PLAYERS] = [(-SIDE_HALF_WIDTH, -SIDE_MARGIN), (SIDE_MARGIN, SIDE_HALF_WIDTH)];
+
+
+/// Half of the playfield a fruit falls on in versus, only the chef with the same id can slice it.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub struct Side(pub usize);
+
+impl Side {
+    pub fn random_x(self, rng: &mut GameRng) -> f32 {
+        let (min, max) = SIDE_SPAWN_BORDERS[self.0 % MAX_PLAYERS];
+        rng.randint(min, max) as f32
+    }
+
+    pub const fn opponent(self) -> Self {
+        Self((self.0 + 1) % MAX_PLAYERS)
+    }
+
+    /// Where the chef of this side can walk.
+    pub fn chef_bounds(self, limit: f32) -> (f32, f32) {
+        if self.0 == 0 { (-limit, -SIDE_GAP) } else { (SIDE_GAP, limit) }
+    }
+}
+
+
+/// Fruit sent over by the opponent's combos.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Garbage {
+    /// Worth nothing, still costs a life when it falls.
+    Rotten,
+    /// Costs a life when sliced, falls harmlessly.
+    Bomb
+}
+
+impl Garbage {
+    pub const fn tint(self) -> Color {
+        match self {
+            Self::Rotten => Color::rgb(0.5, 0.6, 0.35),
+            Self::Bomb => Color::rgb(0.2, 0.15, 0.15)
+        }
+    }
+}
+
+
+/// What a combo of `sliced` fruits sends to the opponent.
+pub const fn garbage_for(sliced: usize) -> Option<(Garbage, usize)> {
+    match sliced {
+        0..=2 => None,
+        3 => Some((Garbage::Rotten, 2)),
+        _ => Some((Garbage::Bomb, 1))
+    }
+}
+
+
+#[derive(Component)]
+struct Divider;
+
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgba(1., 1., 1., 0.25),
+                custom_size: Some(Vec2::new(4., VIRTUAL_HEIGHT)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0., 0., 0.5),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        Divider
+    ));
+}
+
+
+fn show_divider(mode: Res<GameMode>, mut query: Query<&mut Visibility, With<Divider>>) {
+    for mut visibility in &mut query {
+        *visibility = if *mode == GameMode::Versus { Visibility::Inherited } else { Visibility::Hidden };
+    }
+}
+
+
+pub fn send_garbage(
+    mut commands: Commands,
+    fruit_assets: Res<FruitTextures>,
+    mut rng: ResMut<GameRng>,
+    mut slices: EventReader<SliceEvent>,
+    mut text: EventWriter<TextEvent>,
+    mut sound: EventWriter<SoundEvent>
+) {
+    for slice in &mut slices {
+        if slice.boost {
+            continue;
+        }
+
+        let Some((garbage, count)) = garbage_for(slice.fruits.len()) else {
+            continue;
+        };
+
+        let side = Side(slice.player).opponent();
+
+        for _ in 0..count {
+            spawn_garbage(&mut commands, &fruit_assets, &mut rng, side, garbage);
+        }
+
+        let message = match garbage {
+            Garbage::Rotten => format!("+{count} garbage!"),
+            Garbage::Bomb => "Bomb!".to_string()
+        };
+        let x = side.random_x(&mut rng);
+        text.send(TextEvent { text: message, x, y: 200. });
+        sound.send(SoundEvent::sound(SoundType::BOOST).at(Vec2::new(x, 200.)));
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::random::GameRng;
+    use super::{garbage_for, Garbage, Side};
+
+    #[test]
+    fn test_it_sends_garbage_for_combos() {
+        assert_eq!(garbage_for(1), None);
+        assert_eq!(garbage_for(2), None);
+        assert_eq!(garbage_for(3), Some((Garbage::Rotten, 2)));
+        assert_eq!(garbage_for(5), Some((Garbage::Bomb, 1)));
+
+        let mut rng = GameRng::new(7);
+        for _ in 0..100 {
+            assert!(Side(0).random_x(&mut rng) < 0.);
+            assert!(Side(1).random_x(&mut rng) > 0.);
+        }
+        assert!(Side(1).opponent() == Side(0));
+    }
+}
@@ -1,11 +1,82 @@
-use bevy::prelude::States;
+use bevy::prelude::{App, IntoSystemSetConfigs, Res, State, States, SystemSet, Update};
+
+
+/// Size of the playfield in world units, the camera fits it into any window.
+pub const VIRTUAL_WIDTH: f32 = 1140.;
+pub const VIRTUAL_HEIGHT: f32 = 660.;
+
+/// Scale that stretches the full screen pixel art images (152x88) over the playfield.
+pub const SCREEN_SCALE: f32 = VIRTUAL_WIDTH / 152.;
+
+/// Height the chef walks at.
+pub const FLOOR_Y: f32 = -VIRTUAL_HEIGHT / 2. + 110.;
+
+/// How far the chef can walk from the center.
+pub const CHEF_LIMIT: f32 = VIRTUAL_WIDTH / 2. - 70.;
+
+/// How close versus chefs can get to the middle line.
+pub const SIDE_GAP: f32 = 40.;
+
+/// Fruits appear just above the top edge.
+pub const SPAWN_Y: f32 = VIRTUAL_HEIGHT / 2. + 20.;
 
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Default, States)]
 pub enum AppState {
     #[default]
+    Loading,
     MainMenu,
     InGame,
     Paused,
-    Finish
+    Finish,
+    Wardrobe,
+    Achievements,
+    Stats,
+    Settings,
+    Tutorial,
+    /// Bot plays on its own after the main menu sat idle.
+    Demo,
+    Leaderboard
+}
+
+
+/// Fruits fall and can be sliced, in a game, the tutorial and the demo.
+pub fn playing(state: Res<State<AppState>>) -> bool {
+    matches!(state.get(), AppState::InGame | AppState::Tutorial | AppState::Demo)
+}
+
+
+/// Fruits spawn and levels go up on their own, in a game and the demo.
+pub fn in_session(state: Res<State<AppState>>) -> bool {
+    matches!(state.get(), AppState::InGame | AppState::Demo)
+}
+
+
+/// Steps of a frame that decide how a run plays out. They run in this order
+/// in the game and in the replay verifier, so a recorded run plays out the same twice.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameplaySet {
+    Level,
+    Spawn,
+    /// Fills `ChefInput`, everything reading it runs after.
+    Input,
+    Act,
+    Slice,
+    Boost,
+    Fall,
+    Garbage
+}
+
+
+pub fn order_gameplay(app: &mut App) {
+    app.configure_sets(Update, (
+        GameplaySet::Level,
+        GameplaySet::Spawn,
+        GameplaySet::Input,
+        GameplaySet::Act,
+        GameplaySet::Slice,
+        GameplaySet::Boost,
+        GameplaySet::Fall,
+        GameplaySet::Garbage
+    ).chain());
 }
@@ -1,7 +1,14 @@
 use bevy::prelude::*;
 
-use crate::states::session::Session;
+use crate::states::session::{GameMode, Session, MAX_LIVES};
+use crate::stats::{format_time, RunStats};
 use crate::global::AppState;
+use crate::chef::ghost::GhostRun;
+
+const BOOST_ICONS: u32 = 5;
+const HEART_SIZE: f32 = 39.;
+const BOOST_ICON_SIZE: f32 = 36.;
+const SCORE_COUNT_SPEED: f32 = 12.;
 
 
 pub struct InfoPlugin;
@@ -11,101 +18,313 @@ impl Plugin for InfoPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_systems(Startup, setup)
-            .add_systems(OnEnter::<AppState>(AppState::InGame), respawn_hearts)
-            .add_systems(Update, (update, update_live));
+            .add_systems(OnEnter::<AppState>(AppState::InGame), show_hud)
+            .add_systems(OnExit::<AppState>(AppState::InGame), hide_hud)
+            .add_systems(OnEnter::<AppState>(AppState::Demo), show_hud)
+            .add_systems(OnExit::<AppState>(AppState::Demo), hide_hud)
+            .add_systems(Update, (
+                update_level,
+                update_clock,
+                update_score,
+                update_player_scores,
+                update_ghost_delta,
+                update_boosts,
+                update_lives
+            ));
     }
 }
 
 
 #[derive(Component)]
-struct TextInfo;
+struct Hud;
+
+#[derive(Component)]
+struct LevelText;
+
+#[derive(Component)]
+struct ClockText;
+
+/// Score shown on screen, counts up towards `Session::score`.
+#[derive(Component)]
+struct ScoreText {
+    shown: f32
+}
+
+/// Points ahead of or behind the ghost of the best run.
+#[derive(Component)]
+struct GhostDeltaText;
 
+/// Points of each chef, with their lives in versus.
 #[derive(Component)]
-struct Live;
+struct PlayerScoresText;
 
+#[derive(Component)]
+struct BoostIcon(u32);
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+#[derive(Component)]
+struct BoostOverflow;
 
-    let session = Session::default();
+#[derive(Component)]
+struct Live(u32);
 
-    commands.insert_resource(session.clone());
-    let text = session.text();
 
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>
+) {
     let text_style = TextStyle {
         font: asset_server.load("fonts/mn-regular.otf"),
         font_size: 32.,
         color: Color::WHITE
     };
 
-    commands.spawn((Text2dBundle {
-        text: Text::from_section(text, text_style)
-            .with_alignment(TextAlignment::Right),
-        transform: Transform::from_xyz(400., 250., 10.),
+    let boost_atlas = texture_atlases.add(TextureAtlas::from_grid(
+        asset_server.load("images/fruits/pineapple.png"),
+        Vec2::new(60., 60.),
+        8,
+        1,
+        None,
+        None
+    ));
+
+    let root = NodeBundle {
+        style: Style {
+            width: Val::Percent(100.),
+            height: Val::Percent(100.),
+            padding: UiRect::all(Val::Px(16.)),
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::FlexStart,
+            ..default()
+        },
+        visibility: Visibility::Hidden,
+        ..default()
+    };
+
+    let column = |align_items| NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Column,
+            align_items,
+            row_gap: Val::Px(6.),
+            ..default()
+        },
+        ..default()
+    };
+
+    let row = NodeBundle {
+        style: Style {
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(2.),
+            ..default()
+        },
         ..default()
-    }, TextInfo));
+    };
+
+    commands.spawn((root, Hud)).with_children(|root| {
+        // level and elapsed time in the top left corner
+        root.spawn(column(AlignItems::FlexStart)).with_children(|left| {
+            left.spawn((TextBundle::from_section("level 1", text_style.clone()), LevelText));
+            left.spawn((TextBundle::from_section("0:00", text_style.clone()), ClockText));
+        });
+
+        // score, boosts and lives in the top right corner
+        root.spawn(column(AlignItems::FlexEnd)).with_children(|right| {
+            right.spawn((TextBundle::from_section("score 0", text_style.clone()), ScoreText { shown: 0. }));
+            right.spawn((
+                TextBundle::from_section("", TextStyle { font_size: 24., ..text_style.clone() })
+                    .with_style(Style { display: Display::None, ..default() }),
+                PlayerScoresText
+            ));
+            right.spawn((
+                TextBundle::from_section("", TextStyle { font_size: 24., ..text_style.clone() })
+                    .with_style(Style { display: Display::None, ..default() }),
+                GhostDeltaText
+            ));
+
+            right.spawn(row.clone()).with_children(|boosts| {
+                boosts.spawn((TextBundle::from_section("", text_style.clone()), BoostOverflow));
+
+                for i in 0..BOOST_ICONS {
+                    boosts.spawn((
+                        AtlasImageBundle {
+                            style: Style {
+                                width: Val::Px(BOOST_ICON_SIZE),
+                                height: Val::Px(BOOST_ICON_SIZE),
+                                display: Display::None,
+                                ..default()
+                            },
+                            texture_atlas: boost_atlas.clone(),
+                            texture_atlas_image: UiTextureAtlasImage::default(),
+                            ..default()
+                        },
+                        BoostIcon(i)
+                    ));
+                }
+            });
+
+            right.spawn(row).with_children(|lives| {
+                for i in 0..MAX_LIVES {
+                    lives.spawn((
+                        ImageBundle {
+                            style: Style {
+                                width: Val::Px(HEART_SIZE),
+                                height: Val::Px(HEART_SIZE),
+                                ..default()
+                            },
+                            image: asset_server.load("images/heart.png").into(),
+                            ..default()
+                        },
+                        Live(i)
+                    ));
+                }
+            });
+        });
+    });
 }
 
 
-fn respawn_hearts(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let session = Session::default();
+fn show_hud(mut query: Query<&mut Visibility, With<Hud>>) {
+    for mut visibility in &mut query {
+        *visibility = Visibility::Inherited;
+    }
+}
+
 
-    for i in 0..session.lives_left {
-        commands.spawn((
-            SpriteBundle {
-                texture: asset_server.load("images/heart.png"),
-                transform: Transform::from_xyz(530. - 40. * i as f32, 180., 10.).with_scale(Vec3::splat(3.)),
-                ..default()
-            },
-            TextInfo,
-            Live
-        ));
+fn hide_hud(mut query: Query<&mut Visibility, With<Hud>>) {
+    for mut visibility in &mut query {
+        *visibility = Visibility::Hidden;
     }
 }
 
 
-fn update_live(
-    mut commands: Commands, 
-    query: Query<(Entity, &Live)>, 
-    session: Res<Session>,
-    asset_server: Res<AssetServer>
-) {
-    if session.is_changed() {
-        // clear all hearts
-        for (entity, _) in &query {
-            commands.entity(entity).despawn();
+fn update_level(session: Res<Session>, mut query: Query<&mut Text, With<LevelText>>) {
+    if !session.is_changed() {
+        return;
+    }
+
+    for mut text in &mut query {
+        text.sections[0].value = format!("level {}", session.level);
+    }
+}
+
+
+fn update_clock(run: Res<RunStats>, mut query: Query<&mut Text, With<ClockText>>) {
+    if !run.is_changed() {
+        return;
+    }
+
+    for mut text in &mut query {
+        text.sections[0].value = format_time(run.seconds as u32);
+    }
+}
+
+
+fn update_score(time: Res<Time>, session: Res<Session>, mut query: Query<(&mut Text, &mut ScoreText)>) {
+    let target = session.score as f32;
+
+    for (mut text, mut score) in &mut query {
+        if (score.shown - target).abs() < f32::EPSILON {
+            continue;
         }
 
-        for i in 0..(5 - session.lives_left) {
-            commands.spawn((
-                SpriteBundle {
-                    texture: asset_server.load("images/heart-empty.png"),
-                    transform: Transform::from_xyz(370. + 40. * i as f32, 180., 9.).with_scale(Vec3::splat(3.)),
-                    ..default()
-                },
-                TextInfo,
-                Live
-            ));
+        // restarting resets the score, don't count down
+        if score.shown > target {
+            score.shown = target;
+        } else {
+            let step = (target - score.shown).max(1.) * SCORE_COUNT_SPEED * time.delta_seconds();
+            score.shown = (score.shown + step).min(target);
         }
 
-        // respawn all hearts for given lives number
-        for i in 0..session.lives_left {
-            commands.spawn((
-                SpriteBundle {
-                    texture: asset_server.load("images/heart.png"),
-                    transform: Transform::from_xyz(530. - 40. * i as f32, 180., 9.).with_scale(Vec3::splat(3.)),
-                    ..default()
-                },
-                TextInfo,
-                Live
-            ));
+        text.sections[0].value = format!("score {}", score.shown as u32);
+    }
+}
+
+
+fn update_player_scores(
+    session: Res<Session>,
+    mode: Res<GameMode>,
+    mut query: Query<(&mut Text, &mut Style), With<PlayerScoresText>>
+) {
+    if !session.is_changed() && !mode.is_changed() {
+        return;
+    }
+
+    for (mut text, mut style) in &mut query {
+        style.display = if *mode == GameMode::Solo { Display::None } else { Display::Flex };
+
+        let [first, second] = session.player_scores;
+        text.sections[0].value = if *mode == GameMode::Versus {
+            let [first_lives, second_lives] = session.player_lives;
+            format!("P1 {first} ({first_lives} lives)  P2 {second} ({second_lives} lives)")
+        } else {
+            format!("P1 {first}  P2 {second}")
+        };
+    }
+}
+
+
+fn update_ghost_delta(
+    session: Res<Session>,
+    ghost: Res<GhostRun>,
+    mut query: Query<(&mut Text, &mut Style), With<GhostDeltaText>>
+) {
+    if !ghost.is_changed() {
+        return;
+    }
+
+    let delta = ghost.delta(session.score);
+
+    for (mut text, mut style) in &mut query {
+        style.display = if delta.is_some() { Display::Flex } else { Display::None };
+
+        if let Some(delta) = delta {
+            text.sections[0].value = format!("{delta:+} vs best");
+            text.sections[0].style.color = if delta < 0 { Color::rgb(1., 0.5, 0.45) } else { Color::rgb(0.6, 1., 0.6) };
         }
+    }
+}
+
 
+fn update_boosts(
+    session: Res<Session>,
+    mut icons: Query<(&mut Style, &BoostIcon)>,
+    mut overflow: Query<&mut Text, With<BoostOverflow>>
+) {
+    if !session.is_changed() {
+        return;
+    }
+
+    // collapse unused icons so the row stays right aligned
+    for (mut style, icon) in &mut icons {
+        style.display = if icon.0 < session.boosts { Display::Flex } else { Display::None };
+    }
+
+    for mut text in &mut overflow {
+        text.sections[0].value = if session.boosts > BOOST_ICONS {
+            format!("+{}", session.boosts - BOOST_ICONS)
+        } else {
+            String::new()
+        };
     }
 }
 
 
-fn update(mut query: Query<&mut Text, With<TextInfo>>, info: Res<Session>) {
-    for mut text in &mut query {
-        text.sections[0].value = info.text();
+fn update_lives(
+    session: Res<Session>,
+    mode: Res<GameMode>,
+    asset_server: Res<AssetServer>,
+    mut query: Query<(&mut UiImage, &mut Style, &Live)>
+) {
+    if !session.is_changed() && !mode.is_changed() {
+        return;
+    }
+
+    for (mut image, mut style, live) in &mut query {
+        // versus lives are shown next to each player's score
+        style.display = if *mode == GameMode::Versus { Display::None } else { Display::Flex };
+
+        // full hearts stay on the right, lost ones turn empty from the left
+        let texture = if live.0 >= MAX_LIVES.saturating_sub(session.lives_left) { "images/heart.png" } else { "images/heart-empty.png" };
+        image.texture = asset_server.load(texture);
     }
 }
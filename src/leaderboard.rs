@@ -0,0 +1,417 @@
+use std::fmt::Write as _;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::global::AppState;
+use crate::replay::{verify, Replay};
+use crate::states::session::Session;
+use crate::utils::save::SaveData;
+
+/// Save key of the leaderboard url, the leaderboard stays offline without it.
+const URL_KEY: &str = "leaderboard.url";
+const TIMEOUT: Duration = Duration::from_secs(5);
+pub const TOP_SCORES: usize = 10;
+
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Startup, setup)
+            .add_systems(OnEnter::<AppState>(AppState::Finish), submit_run)
+            .add_systems(Update, finish_submissions)
+        ;
+    }
+}
+
+
+/// Where scores go, parsed from `http://host[:port]/path`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Endpoint {
+    host: String,
+    port: u16,
+    path: String
+}
+
+impl Endpoint {
+    pub fn parse(url: &str) -> Option<Self> {
+        let rest = url.trim().strip_prefix("http://")?;
+        let (address, path) = rest.find('/').map_or((rest, "/"), |slash| rest.split_at(slash));
+
+        let (host, port) = match address.split_once(':') {
+            Some((host, port)) => (host, port.parse().ok()?),
+            None => (address, 80)
+        };
+
+        if host.is_empty() {
+            return None;
+        }
+
+        Some(Self { host: host.to_string(), port, path: path.to_string() })
+    }
+}
+
+
+#[derive(Resource, Default)]
+pub struct Leaderboard {
+    pub endpoint: Option<Endpoint>
+}
+
+
+/// A finished run as the server sees it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub score: u32,
+    pub mode: &'static str,
+    pub seed: u64,
+    /// The run's whole record with its input log, so the server can play it again.
+    pub record: String
+}
+
+impl Submission {
+    fn body(&self) -> String {
+        format!("score={}&mode={}&seed={}&replay={}", self.score, self.mode, self.seed, url_encode(&self.record))
+    }
+}
+
+
+/// Percent-encodes everything but unreserved characters, for form bodies.
+fn url_encode(value: &str) -> String {
+    value.bytes().fold(String::new(), |mut encoded, byte| {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+        encoded
+    })
+}
+
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub score: u32,
+    pub mode: String
+}
+
+
+/// FNV-1a, stable across builds unlike the std hasher.
+pub fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3))
+}
+
+
+/// Sends a request and returns the body of a 2xx response.
+fn request(endpoint: &Endpoint, method: &str, target: &str, body: &str) -> io::Result<String> {
+    let address = (endpoint.host.as_str(), endpoint.port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "leaderboard host not found"))?;
+
+    let mut stream = TcpStream::connect_timeout(&address, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    // HTTP/1.0 keeps the response unchunked and closes the connection after it
+    let message = format!(
+        "{method} {target} HTTP/1.0\r\nHost: {}\r\nContent-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n\r\n{body}",
+        endpoint.host,
+        body.len()
+    );
+    stream.write_all(message.as_bytes())?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+    let status = head.split_whitespace().nth(1).and_then(|code| code.parse::<u16>().ok()).unwrap_or(0);
+
+    if !(200..300).contains(&status) {
+        return Err(io::Error::other(format!("leaderboard answered {status}")));
+    }
+
+    Ok(body.to_string())
+}
+
+
+pub fn submit(endpoint: &Endpoint, submission: &Submission) -> io::Result<()> {
+    request(endpoint, "POST", &endpoint.path, &submission.body()).map(|_| ())
+}
+
+
+/// Best scores, one `score mode` pair per line of the response.
+pub fn fetch_top(endpoint: &Endpoint, limit: usize) -> io::Result<Vec<Entry>> {
+    let body = request(endpoint, "GET", &format!("{}?limit={limit}", endpoint.path), "")?;
+
+    let entries = body
+        .lines()
+        .filter_map(|line| {
+            let (score, mode) = line.trim().split_once(' ')?;
+            Some(Entry { score: score.parse().ok()?, mode: mode.trim().to_string() })
+        })
+        .take(limit)
+        .collect();
+
+    Ok(entries)
+}
+
+
+/// Runs a request on its own thread so the frame never waits for the network.
+pub struct Pending<T>(Arc<Mutex<Option<T>>>);
+
+impl<T: Send + 'static> Pending<T> {
+    pub fn spawn(job: impl FnOnce() -> T + Send + 'static) -> Self {
+        let result = Arc::new(Mutex::new(None));
+        let slot = Arc::clone(&result);
+
+        thread::spawn(move || {
+            let value = job();
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(value);
+            }
+        });
+
+        Self(result)
+    }
+
+    /// The result once the request is done, `None` while it is still running.
+    pub fn take(&self) -> Option<T> {
+        self.0.try_lock().ok()?.take()
+    }
+}
+
+
+#[derive(Component)]
+struct Submitting(Pending<io::Result<()>>);
+
+
+fn setup(mut commands: Commands, save: Res<SaveData>) {
+    let endpoint = save.get(URL_KEY).and_then(Endpoint::parse);
+    commands.insert_resource(Leaderboard { endpoint });
+}
+
+
+fn submit_run(
+    mut commands: Commands,
+    leaderboard: Res<Leaderboard>,
+    session: Res<Session>,
+    replay: Res<Replay>
+) {
+    let Some(endpoint) = leaderboard.endpoint.clone() else {
+        return;
+    };
+
+    let submission = Submission {
+        score: session.score,
+        mode: replay.mode.name(),
+        seed: replay.seed,
+        record: replay.to_record(session.score)
+    };
+
+    commands.spawn(Submitting(Pending::spawn(move || {
+        // only a run that plays out the same again goes on the board, the server can check it the same way
+        verify(&submission.record).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
+        submit(&endpoint, &submission)
+    })));
+}
+
+
+fn finish_submissions(mut commands: Commands, query: Query<(&Submitting, Entity)>) {
+    for (submitting, entity) in &query {
+        let Some(result) = submitting.0.take() else {
+            continue;
+        };
+
+        // the local record is already written, a lost submission only misses the global board
+        if let Err(error) = result {
+            warn!("failed to submit score: {error}");
+        }
+
+        commands.entity(entity).despawn();
+    }
+}
+
+
+
+#[cfg(test)]
+pub mod stub {
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    use super::Endpoint;
+
+    /// Answers `responses` connections with a canned body and returns the requests it got.
+    pub fn stub_server(status: &'static str, body: &'static str, responses: usize) -> (Endpoint, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = thread::spawn(move || {
+            listener.incoming().take(responses).map(|stream| {
+                let mut stream = stream.unwrap();
+                let request = read_request(&mut stream);
+
+                write!(stream, "HTTP/1.0 {status}\r\nContent-Length: {}\r\n\r\n{body}", body.len()).unwrap();
+                request
+            }).collect()
+        });
+
+        (Endpoint::parse(&format!("http://127.0.0.1:{port}/scores")).unwrap(), server)
+    }
+
+    fn read_request(stream: &mut TcpStream) -> String {
+        let mut request = Vec::new();
+        let mut buffer = [0; 1024];
+
+        loop {
+            let read = stream.read(&mut buffer).unwrap();
+            request.extend_from_slice(&buffer[..read]);
+
+            let text = String::from_utf8_lossy(&request).to_string();
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Content-Length: "))
+                    .and_then(|length| length.parse().ok())
+                    .unwrap_or(0);
+
+                if body.len() >= length || read == 0 {
+                    return text;
+                }
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+
+    use crate::global::AppState;
+    use crate::replay::{bot_run, Replay};
+    use crate::states::session::{GameMode, Session};
+    use crate::utils::save::SaveData;
+    use super::stub::stub_server;
+    use super::{fetch_top, submit, Endpoint, Entry, LeaderboardPlugin, Pending, Submission, Submitting, URL_KEY};
+
+    fn finish_app(url: &str, score: u32, replay: Replay) -> App {
+        let mut save = SaveData::default();
+        save.set(URL_KEY, url);
+
+        let mut app = App::new();
+        app
+            .add_plugins(MinimalPlugins)
+            .add_state::<AppState>()
+            .insert_resource(save)
+            .insert_resource(Session { score, ..Session::default() })
+            .insert_resource(replay)
+            .add_plugins(LeaderboardPlugin);
+
+        app.world.resource_mut::<NextState<AppState>>().set(AppState::Finish);
+        app.update();
+        app
+    }
+
+    fn submissions(app: &mut App) -> usize {
+        app.world.query::<&Submitting>().iter(&app.world).count()
+    }
+
+    /// Runs frames until every submission thread is done.
+    fn finish_submitting(app: &mut App) {
+        for _ in 0..1000 {
+            if submissions(app) == 0 {
+                return;
+            }
+            thread::sleep(Duration::from_millis(5));
+            app.update();
+        }
+        panic!("submission never finished");
+    }
+
+    #[test]
+    fn test_it_parses_endpoints() {
+        let endpoint = Endpoint::parse("http://example.com:8080/api/scores").unwrap();
+        assert_eq!((endpoint.host.as_str(), endpoint.port, endpoint.path.as_str()), ("example.com", 8080, "/api/scores"));
+
+        assert_eq!(Endpoint::parse("http://example.com").unwrap().port, 80);
+        assert_eq!(Endpoint::parse("https://example.com"), None);
+        assert_eq!(Endpoint::parse("http://:80/"), None);
+    }
+
+    #[test]
+    fn test_it_submits_and_fetches_scores() {
+        let (endpoint, server) = stub_server("200 OK", "120 solo\n95 versus\nbroken\n", 2);
+
+        let submission = Submission { score: 120, mode: "solo", seed: 7, record: "120\nseed=7\ninputs=2*00".to_string() };
+        let pending = Pending::spawn(move || {
+            submit(&endpoint, &submission).unwrap();
+            fetch_top(&endpoint, 10).unwrap()
+        });
+
+        let entries = loop {
+            if let Some(entries) = pending.take() {
+                break entries;
+            }
+            thread::yield_now();
+        };
+
+        assert_eq!(entries, vec![
+            Entry { score: 120, mode: "solo".to_string() },
+            Entry { score: 95, mode: "versus".to_string() }
+        ]);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /scores HTTP/1.0"));
+        assert!(requests[0].ends_with("score=120&mode=solo&seed=7&replay=120%0Aseed%3D7%0Ainputs%3D2%2A00"));
+        assert!(requests[1].starts_with("GET /scores?limit=10 HTTP/1.0"));
+    }
+
+    #[test]
+    fn test_it_reports_server_errors_and_offline_servers() {
+        let (endpoint, server) = stub_server("500 Internal Server Error", "", 1);
+        assert!(fetch_top(&endpoint, 10).is_err());
+        server.join().unwrap();
+
+        // nothing listens on the port anymore
+        assert!(fetch_top(&endpoint, 10).is_err());
+    }
+
+    #[test]
+    fn test_it_submits_finished_runs() {
+        let (score, replay) = bot_run(GameMode::Solo, 5, 1, 600);
+        let (endpoint, server) = stub_server("200 OK", "", 1);
+
+        let mut app = finish_app(&format!("http://127.0.0.1:{}/scores", endpoint.port), score, replay);
+        assert_eq!(submissions(&mut app), 1);
+
+        finish_submitting(&mut app);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /scores HTTP/1.0"));
+        assert!(requests[0].contains(&format!("score={score}&mode=solo&seed=5&replay={score}%0Aseed%3D5")));
+    }
+
+    #[test]
+    fn test_it_keeps_runs_that_dont_play_out_off_the_board() {
+        let (score, replay) = bot_run(GameMode::Solo, 5, 1, 600);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let mut app = finish_app(&format!("http://127.0.0.1:{port}/scores"), score + 100, replay);
+        finish_submitting(&mut app);
+
+        assert!(listener.accept().is_err());
+    }
+}
@@ -2,9 +2,23 @@ use bevy::prelude::*;
 
 use crate::components::Clock;
 use crate::states::session::Session;
-use crate::global::AppState;
+use crate::global::{in_session, AppState, GameplaySet};
+#[cfg(feature = "dev")]
+use crate::{chef::Player, console::{arg, ConsoleExt}, fruits::spawn::SpawnTimer};
 
 const LEVEL_UPDATE_TIME: f32 = 6.;
+/// Highest level a run can start at, a level every few seconds makes it well over an hour in.
+pub const MAX_LEVEL: u32 = 1000;
+
+const FROM_MENU_ENTER: OnTransition<AppState> = OnTransition::<AppState> {
+    from: AppState::MainMenu,
+    to: AppState::InGame
+};
+
+const FROM_FINISH_ENTER: OnTransition<AppState> = OnTransition::<AppState> {
+    from: AppState::Finish,
+    to: AppState::InGame
+};
 
 
 pub struct LevelPlugin;
@@ -13,9 +27,16 @@ pub struct LevelPlugin;
 impl Plugin for LevelPlugin {
     fn build(&self, app: &mut App) {
         app
+            .init_resource::<StartLevel>()
             .add_systems(Startup, startup)
-            .add_systems(Update, update_level.run_if(in_state(AppState::InGame)))
+            .add_systems(FROM_MENU_ENTER, startup)
+            .add_systems(FROM_FINISH_ENTER, startup)
+            .add_systems(OnEnter::<AppState>(AppState::Demo), startup)
+            .add_systems(Update, update_level.in_set(GameplaySet::Level).run_if(in_session))
             .add_event::<LevelUpdate>();
+
+        #[cfg(feature = "dev")]
+        app.add_console_command("level", "level <n>", level_command);
     }
 }
 
@@ -23,8 +44,18 @@ impl Plugin for LevelPlugin {
 
 #[derive(Event)]
 pub struct LevelUpdate {
-    #[allow(dead_code)]
-    number: u32
+    pub number: u32
+}
+
+
+/// Level every run starts at, later levels spawn faster and speed up the chef.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StartLevel(pub u32);
+
+impl Default for StartLevel {
+    fn default() -> Self {
+        Self(1)
+    }
 }
 
 
@@ -35,13 +66,17 @@ struct Level {
 }
 
 
-fn startup(mut commands: Commands) {
+fn startup(mut commands: Commands, start: Res<StartLevel>, session: Option<ResMut<Session>>) {
     commands.insert_resource(
         Level {
             clock: Clock::seconds(LEVEL_UPDATE_TIME),
-            number: 1
+            number: start.0
         }
     );
+
+    if let Some(mut session) = session {
+        session.level = start.0;
+    }
 }
 
 
@@ -59,3 +94,23 @@ fn update_level(
         session.level = level.number;
     }
 }
+
+
+/// Jumps straight to a level, with the spawn pace and chef speed it would have by then.
+#[cfg(feature = "dev")]
+fn level_command(world: &mut World, args: &[&str]) -> Result<String, String> {
+    let number: u32 = arg(args, 0)?;
+    if number == 0 {
+        return Err("levels start at 1".to_string());
+    }
+
+    world.get_resource_mut::<Level>().ok_or("no level to change")?.number = number;
+    world.resource_mut::<Session>().level = number;
+    world.insert_resource(SpawnTimer::at_level(number));
+
+    for mut player in world.query::<&mut Player>().iter_mut(world) {
+        player.set_level(number);
+    }
+
+    Ok(format!("level {number}"))
+}
@@ -16,7 +16,10 @@
     clippy::suboptimal_flops, 
     clippy::needless_pass_by_value, 
     clippy::module_name_repetitions,
-    clippy::too_many_arguments
+    clippy::too_many_arguments,
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
 )]
 
 
@@ -25,13 +28,30 @@
 
 use std::time::Duration;
 
-use bevy::{prelude::*, asset::ChangeWatcher, audio::VolumeLevel};
+use bevy::{prelude::*, asset::ChangeWatcher};
+use bevy::log::{Level, LogPlugin};
 
 mod chef;
 mod fruits;
 mod level;
 mod info;
 mod sound;
+mod music;
+mod display;
+mod achievements;
+mod stats;
+mod assets;
+mod animation;
+mod camera;
+mod settings;
+mod particles;
+mod leaderboard;
+mod cli;
+mod replay;
+#[cfg(feature = "dev")]
+mod console;
+#[cfg(feature = "dev")]
+mod debug;
 
 mod states;
 mod utils;
@@ -41,31 +61,111 @@ mod components;
 
 
 fn main() {
-    std::env::set_var("RUST_LOG", "symphonia_core=error");
+    let cli = match cli::parse(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(error) => {
+            eprintln!("{error}\n\n{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    // saves and records are kept next to the working directory
+    if let Some(dir) = &cli.data_dir {
+        if let Err(error) = std::fs::create_dir_all(dir).and_then(|()| std::env::set_current_dir(dir)) {
+            eprintln!("can't use {} as data dir: {error}", dir.display());
+            std::process::exit(2);
+        }
+    }
+
+    match &cli.command {
+        cli::Command::Help => {
+            println!("{}", cli::USAGE);
+            return;
+        }
+        cli::Command::Verify(path) => std::process::exit(replay::verify_command(path)),
+        cli::Command::Play => {}
+    }
+
+    if cli.headless {
+        std::process::exit(replay::run_headless(&cli));
+    }
+
+    // a watched replay decides how the run is set up
+    let playback = cli.replay.as_ref().map(|path| match replay::read_replay(path) {
+        Ok((_, replay)) => replay,
+        Err(error) => {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
+    });
+    let mode = playback.as_ref().map(|replay| replay.mode).or(cli.mode);
+    let level = playback.as_ref().map(|replay| replay.level).or(cli.level).unwrap_or(1);
+
+    let mut app = App::new();
 
-    App::new()
-        .add_plugins(settings())
+    app
+        .add_plugins(settings(&cli))
         .add_state::<global::AppState>()
         .add_plugins((
             states::menu   ::MenuPlugin, 
             states::session::SessionPlugin, 
             states::pause  ::PausePlugin, 
             states::finish ::FinishPlugin,
+            states::wardrobe::WardrobePlugin,
+            states::achievements::AchievementsScreenPlugin,
+            achievements::AchievementsPlugin,
+            states::stats::StatsScreenPlugin,
+            stats::StatsPlugin,
             sound  ::SoundPlugin, 
+            music  ::MusicPlugin,
+            utils::save::SavePlugin,
+            display::DisplayPlugin,
+            states::loading::LoadingPlugin,
         ))
-        .add_systems(Startup, setup)
+        .add_plugins((
+            animation::SpriteAnimationPlugin,
+            settings::SettingsPlugin,
+            states::settings::SettingsScreenPlugin,
+            camera::CameraEffectsPlugin,
+            particles::ParticlesPlugin,
+            states::tutorial::TutorialPlugin,
+            states::demo::DemoPlugin,
+            states::leaderboard::LeaderboardScreenPlugin,
+            leaderboard::LeaderboardPlugin,
+            replay::ReplayPlugin,
+        ))
+        .insert_resource(level::StartLevel(level))
+        .insert_resource(replay::ChosenSeed(cli.seed))
+        .add_systems(Startup, setup);
+
+    #[cfg(feature = "dev")]
+    app.add_plugins((console::ConsolePlugin, debug::DebugPlugin));
+
+    if let Some(mode) = mode {
+        app.insert_resource(mode);
+    }
+    if let Some(replay) = playback {
+        app.insert_resource(replay::Playback::new(replay));
+    }
+
+    app
+        .insert_resource(cli)
         .run();
 }
 
 
 
-fn settings() -> bevy::app::PluginGroupBuilder {
+fn settings(cli: &cli::Cli) -> bevy::app::PluginGroupBuilder {
+    let resolution = cli.resolution.unwrap_or(Vec2::new(global::VIRTUAL_WIDTH, global::VIRTUAL_HEIGHT));
+
     DefaultPlugins
         .set(ImagePlugin::default_nearest())
         .set(WindowPlugin {
             primary_window: Some(Window {
                 title: "Fruits game!".into(),
-                resolution: (1140., 660.).into(),
+                resolution: resolution.into(),
+                mode: cli.window_mode.unwrap_or_default(),
+                fit_canvas_to_parent: true,
                 ..default()
             }),
             ..default()
@@ -74,25 +174,21 @@ fn settings() -> bevy::app::PluginGroupBuilder {
             watch_for_changes: ChangeWatcher::with_delay(Duration::from_secs(1)),
             ..default()
         })
+        // RUST_LOG still overrides this
+        .set(LogPlugin {
+            level: cli.log_level.unwrap_or(Level::INFO),
+            filter: "wgpu=error,naga=warn,symphonia_core=error".to_string()
+        })
+        // all audio goes through bevy_kira_audio
+        .disable::<bevy::audio::AudioPlugin>()
 }
 
 
 fn setup(mut commands: Commands, assert_server: Res<AssetServer>) {
-    commands.spawn(Camera2dBundle::default());
     // add background image
     commands.spawn(SpriteBundle {
         texture: assert_server.load("images/bg.png"),
-        transform: Transform::from_xyz(0., 0., -1.).with_scale(Vec3::splat(7.5)),
+        transform: Transform::from_xyz(0., 0., -1.).with_scale(Vec3::splat(global::SCREEN_SCALE)),
         ..default()
     });
-
-    commands.spawn(AudioBundle {
-        source: assert_server.load("audio/bg.ogg"),
-        settings: PlaybackSettings { 
-            mode: bevy::audio::PlaybackMode::Loop, 
-            speed: 2.0, 
-            paused: false,
-            volume: bevy::audio::Volume::Absolute(VolumeLevel::new(0.3))
-        }
-    });
 }
@@ -0,0 +1,158 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+
+use crate::global::AppState;
+use crate::level::LevelUpdate;
+use crate::sound::MusicChannel;
+
+const CROSSFADE: Duration = Duration::from_millis(1200);
+const DUCK_FADE: Duration = Duration::from_millis(300);
+const RATE_FADE: Duration = Duration::from_secs(2);
+
+/// Volume multiplier while the game is paused.
+const DUCK_VOLUME: f64 = 0.35;
+/// Playback rate added per level, capped at `MAX_INTENSITY`.
+const INTENSITY_STEP: f64 = 0.02;
+const MAX_INTENSITY: f64 = 0.4;
+
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(Music::default())
+            .add_systems(Update, (switch_track, intensify))
+            .add_systems(OnEnter::<AppState>(AppState::Paused), duck)
+            .add_systems(OnExit::<AppState>(AppState::Paused), unduck)
+        ;
+    }
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Track {
+    pub file: &'static str,
+    pub volume: f64,
+    pub rate: f64
+}
+
+pub const MENU_TRACK: Track = Track { file: "audio/bg.ogg", volume: 0.25, rate: 1.0 };
+pub const GAME_TRACK: Track = Track { file: "audio/bg.ogg", volume: 0.3, rate: 2.0 };
+pub const FINISH_TRACK: Track = Track { file: "audio/bg.ogg", volume: 0.2, rate: 0.8 };
+
+
+pub const fn track_for(state: &AppState) -> Track {
+    match state {
+        AppState::InGame | AppState::Paused => GAME_TRACK,
+        AppState::Finish => FINISH_TRACK,
+        _ => MENU_TRACK
+    }
+}
+
+
+/// Playback rate of the game track at the given level.
+pub fn intensity_rate(track: Track, level: u32) -> f64 {
+    let intensity = (f64::from(level.saturating_sub(1)) * INTENSITY_STEP).min(MAX_INTENSITY);
+    track.rate + intensity
+}
+
+
+#[derive(Resource, Default)]
+struct Music {
+    track: Option<Track>,
+    instance: Option<Handle<AudioInstance>>
+}
+
+
+fn switch_track(
+    state: Res<State<AppState>>,
+    asset_server: Res<AssetServer>,
+    channel: Res<AudioChannel<MusicChannel>>,
+    mut music: ResMut<Music>,
+    mut instances: ResMut<Assets<AudioInstance>>
+) {
+    let track = track_for(state.get());
+
+    if music.track == Some(track) {
+        return;
+    }
+
+    if let Some(instance) = music.instance.take().and_then(|handle| instances.get_mut(&handle)) {
+        instance.stop(AudioTween::linear(CROSSFADE));
+    }
+
+    let instance = channel
+        .play(asset_server.load(track.file))
+        .looped()
+        .with_volume(track.volume)
+        .with_playback_rate(track.rate)
+        .fade_in(AudioTween::linear(CROSSFADE))
+        .handle();
+
+    music.track = Some(track);
+    music.instance = Some(instance);
+}
+
+
+fn intensify(
+    mut events: EventReader<LevelUpdate>,
+    music: Res<Music>,
+    mut instances: ResMut<Assets<AudioInstance>>
+) {
+    let Some(level) = events.iter().last() else {
+        return;
+    };
+
+    if music.track != Some(GAME_TRACK) {
+        return;
+    }
+
+    if let Some(instance) = music.instance.as_ref().and_then(|handle| instances.get_mut(handle)) {
+        instance.set_playback_rate(intensity_rate(GAME_TRACK, level.number), AudioTween::linear(RATE_FADE));
+    }
+}
+
+
+fn set_volume(music: &Music, instances: &mut Assets<AudioInstance>, volume: f64) {
+    if let Some(instance) = music.instance.as_ref().and_then(|handle| instances.get_mut(handle)) {
+        instance.set_volume(Volume::Amplitude(volume), AudioTween::linear(DUCK_FADE));
+    }
+}
+
+
+fn duck(music: Res<Music>, mut instances: ResMut<Assets<AudioInstance>>) {
+    if let Some(track) = music.track {
+        set_volume(&music, &mut instances, track.volume * DUCK_VOLUME);
+    }
+}
+
+
+fn unduck(music: Res<Music>, mut instances: ResMut<Assets<AudioInstance>>) {
+    if let Some(track) = music.track {
+        set_volume(&music, &mut instances, track.volume);
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::global::AppState;
+    use super::{intensity_rate, track_for, GAME_TRACK, MAX_INTENSITY};
+
+    #[test]
+    fn test_it_keeps_game_track_while_paused() {
+        assert_eq!(track_for(&AppState::Paused), track_for(&AppState::InGame));
+        assert_ne!(track_for(&AppState::Finish), track_for(&AppState::InGame));
+    }
+
+    #[test]
+    fn test_it_caps_intensity() {
+        assert!((intensity_rate(GAME_TRACK, 1) - GAME_TRACK.rate).abs() < f64::EPSILON);
+        assert!(intensity_rate(GAME_TRACK, 5) > GAME_TRACK.rate);
+        assert!((intensity_rate(GAME_TRACK, 500) - (GAME_TRACK.rate + MAX_INTENSITY)).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,243 @@
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+
+use crate::global::AppState;
+use crate::utils::random::uniform;
+
+
+pub struct ParticlesPlugin;
+
+impl Plugin for ParticlesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (emit, update_particles).run_if(not(in_state(AppState::Paused))));
+    }
+}
+
+
+/// How an emitter spawns its particles. Ranges are `(min, max)`, over life values are `(start, end)`.
+#[derive(Clone, Copy, Debug)]
+pub struct EmitterSpec {
+    /// Particles spawned right away.
+    pub burst: u32,
+    /// Particles per second while the emitter is alive.
+    pub rate: f32,
+    /// Seconds the emitter keeps emitting before it despawns.
+    pub duration: f32,
+    pub lifetime: (f32, f32),
+    pub speed: (f32, f32),
+    /// Center of the velocity cone in radians, 0 points right.
+    pub direction: f32,
+    /// Half angle of the velocity cone.
+    pub spread: f32,
+    /// Vertical acceleration, negative pulls down.
+    pub gravity: f32,
+    pub color: (Color, Color),
+    pub size: (f32, f32),
+    /// Half size of the area particles appear in around the emitter.
+    pub area: Vec2
+}
+
+impl EmitterSpec {
+    pub const DEFAULT: Self = Self {
+        burst: 0,
+        rate: 0.,
+        duration: 0.,
+        lifetime: (0.5, 1.),
+        speed: (100., 200.),
+        direction: std::f32::consts::FRAC_PI_2,
+        spread: std::f32::consts::PI,
+        gravity: 0.,
+        color: (Color::WHITE, Color::rgba(1., 1., 1., 0.)),
+        size: (8., 8.),
+        area: Vec2::ZERO
+    };
+}
+
+
+/// Inserts the owner's marker into an emitter and every particle it spawns,
+/// so the owner can despawn them along with the rest of its entities.
+pub type Tag = fn(&mut EntityCommands);
+
+
+#[derive(Component)]
+pub struct Emitter {
+    spec: EmitterSpec,
+    age: f32,
+    /// Fraction of a particle carried over to the next frame.
+    pending: f32,
+    burst_done: bool,
+    tag: Option<Tag>
+}
+
+impl Emitter {
+    pub const fn new(spec: EmitterSpec) -> Self {
+        Self { spec, age: 0., pending: 0., burst_done: false, tag: None }
+    }
+
+    /// Number of particles to spawn after `delta` seconds.
+    pub fn due(&mut self, delta: f32) -> u32 {
+        let mut count = 0;
+
+        if !self.burst_done {
+            self.burst_done = true;
+            count += self.spec.burst;
+        }
+
+        let active = (self.spec.duration - self.age).clamp(0., delta);
+        self.age += delta;
+        self.pending += self.spec.rate * active;
+
+        let whole = self.pending.floor();
+        self.pending -= whole;
+
+        count + whole as u32
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.burst_done && self.age >= self.spec.duration
+    }
+}
+
+
+#[derive(Component)]
+struct Particle {
+    velocity: Vec2,
+    gravity: f32,
+    age: f32,
+    lifetime: f32,
+    color: (Color, Color),
+    size: (f32, f32)
+}
+
+
+/// Spawns a one-off emitter at `position`.
+pub fn spawn_emitter(commands: &mut Commands, spec: EmitterSpec, position: Vec3) -> Entity {
+    commands.spawn((Emitter::new(spec), TransformBundle::from_transform(Transform::from_translation(position)))).id()
+}
+
+
+/// Spawns an emitter whose particles get `tag` as well, for effects that go away with a screen.
+pub fn spawn_tagged_emitter(commands: &mut Commands, spec: EmitterSpec, position: Vec3, tag: Tag) -> Entity {
+    let emitter = Emitter { tag: Some(tag), ..Emitter::new(spec) };
+    let mut entity = commands.spawn((emitter, TransformBundle::from_transform(Transform::from_translation(position))));
+    tag(&mut entity);
+    entity.id()
+}
+
+
+pub fn lerp_color(from: Color, to: Color, t: f32) -> Color {
+    let [r1, g1, b1, a1] = from.as_rgba_f32();
+    let [r2, g2, b2, a2] = to.as_rgba_f32();
+    let t = t.clamp(0., 1.);
+
+    Color::rgba(r1 + (r2 - r1) * t, g1 + (g2 - g1) * t, b1 + (b2 - b1) * t, a1 + (a2 - a1) * t)
+}
+
+
+fn between((min, max): (f32, f32)) -> f32 {
+    if max > min { uniform(f64::from(min), f64::from(max)) as f32 } else { min }
+}
+
+
+fn emit(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut emitters: Query<(&mut Emitter, &GlobalTransform, Entity)>
+) {
+    for (mut emitter, transform, entity) in &mut emitters {
+        let spec = emitter.spec;
+        let origin = transform.translation();
+
+        for _ in 0..emitter.due(time.delta_seconds()) {
+            let angle = spec.direction + between((-spec.spread, spec.spread));
+            let velocity = Vec2::from_angle(angle) * between(spec.speed);
+            let offset = Vec3::new(between((-spec.area.x, spec.area.x)), between((-spec.area.y, spec.area.y)), 0.);
+
+            let mut particle = commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: spec.color.0,
+                        custom_size: Some(Vec2::splat(spec.size.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(origin + offset),
+                    ..default()
+                },
+                Particle {
+                    velocity,
+                    gravity: spec.gravity,
+                    age: 0.,
+                    lifetime: between(spec.lifetime),
+                    color: spec.color,
+                    size: spec.size
+                }
+            ));
+
+            if let Some(tag) = emitter.tag {
+                tag(&mut particle);
+            }
+        }
+
+        if emitter.is_done() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+
+fn update_particles(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut particles: Query<(&mut Particle, &mut Transform, &mut Sprite, Entity)>
+) {
+    let delta = time.delta_seconds();
+
+    for (mut particle, mut transform, mut sprite, entity) in &mut particles {
+        particle.age += delta;
+
+        if particle.age >= particle.lifetime {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        particle.velocity.y += particle.gravity * delta;
+        transform.translation += (particle.velocity * delta).extend(0.);
+
+        let life = particle.age / particle.lifetime;
+        let (start, end) = particle.size;
+
+        sprite.color = lerp_color(particle.color.0, particle.color.1, life);
+        sprite.custom_size = Some(Vec2::splat(start + (end - start) * life));
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Color;
+    use super::{lerp_color, Emitter, EmitterSpec};
+
+    #[test]
+    fn test_it_spreads_rate_over_frames() {
+        let mut emitter = Emitter::new(EmitterSpec { burst: 5, rate: 8., duration: 1., ..EmitterSpec::DEFAULT });
+
+        // burst plus 8 per second, fractions carry over
+        assert_eq!(emitter.due(0.0625), 5);
+        assert_eq!(emitter.due(0.125), 1);
+        let rest: u32 = (0..10).map(|_| emitter.due(0.125)).sum();
+
+        assert_eq!(rest, 7);
+        assert!(emitter.is_done());
+    }
+
+    #[test]
+    fn test_it_blends_colors() {
+        let color = lerp_color(Color::rgba(0., 0., 0., 1.), Color::rgba(1., 0.5, 0., 0.), 0.5);
+        let expected = [0.5, 0.25, 0., 0.5];
+
+        for (channel, expected) in color.as_rgba_f32().into_iter().zip(expected) {
+            assert!((channel - expected).abs() < f32::EPSILON);
+        }
+    }
+}
@@ -0,0 +1,671 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+
+use crate::animation::{Clip, SpriteAnimation};
+use crate::chef::bot::{drive_bot, Bot, Difficulty};
+use crate::chef::chef::{self, start_x};
+use crate::chef::input::ChefInput;
+use crate::chef::{ChefHitEvent, Player};
+use crate::cli::Cli;
+use crate::fruits::boost::{process_boost, spawn_boost, BoostEvent, BoostUsedEvent};
+use crate::fruits::fruit::{self, SliceEvent};
+use crate::fruits::penalty::WaveEvent;
+use crate::fruits::spawn::{increase_spawn_intensity, spawn_fruits, SpawnTimer};
+use crate::fruits::splash::SplashEvent;
+use crate::fruits::sprite::FruitTextures;
+use crate::fruits::text::TextEvent;
+use crate::fruits::versus::send_garbage;
+use crate::global::{order_gameplay, AppState, GameplaySet, FLOOR_Y};
+use crate::leaderboard::checksum;
+use crate::level::{LevelPlugin, StartLevel, MAX_LEVEL};
+use crate::sound::SoundEvent;
+use crate::states::session::{GameMode, Session, MAX_PLAYERS};
+use crate::utils::random::GameRng;
+
+/// Bot runs step the game as if it ran at 60 fps.
+const BOT_FRAME: Duration = Duration::from_nanos(16_666_667);
+/// Ten minutes, bots that never lose stop there.
+const BOT_RUN_FRAMES: usize = 60 * 60 * 10;
+/// An hour at 240 fps, longer logs are refused before they are unpacked.
+pub const MAX_RUN_FRAMES: usize = 240 * 60 * 60;
+
+
+const FROM_MENU_ENTER: OnTransition<AppState> = OnTransition::<AppState> {
+    from: AppState::MainMenu,
+    to: AppState::InGame
+};
+
+const FROM_FINISH_ENTER: OnTransition<AppState> = OnTransition::<AppState> {
+    from: AppState::Finish,
+    to: AppState::InGame
+};
+
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(Replay::new(0, GameMode::Solo, 1))
+            .init_resource::<ChosenSeed>()
+            .add_systems(FROM_MENU_ENTER, start_recording)
+            .add_systems(FROM_FINISH_ENTER, start_recording)
+            .add_systems(OnEnter::<AppState>(AppState::MainMenu), start_playback.run_if(resource_exists::<Playback>()))
+            .add_systems(OnEnter::<AppState>(AppState::Finish), (save_to_file, stop_playback))
+            .add_systems(Update, (play_back.run_if(resource_exists::<Playback>()), record_frame)
+                .chain()
+                .in_set(GameplaySet::Input)
+                .after(drive_bot)
+                .run_if(in_state(AppState::InGame))
+            )
+        ;
+    }
+}
+
+
+/// Everything needed to play a run again: its seed, and how long each frame took and what every chef pressed.
+#[derive(Resource, Clone, Debug, PartialEq, Eq)]
+pub struct Replay {
+    pub seed: u64,
+    pub mode: GameMode,
+    pub level: u32,
+    frames: Vec<Frame>
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Frame {
+    delta: Duration,
+    inputs: [u8; MAX_PLAYERS]
+}
+
+
+impl Replay {
+    pub const fn new(seed: u64, mode: GameMode, level: u32) -> Self {
+        Self { seed, mode, level, frames: Vec::new() }
+    }
+
+    fn body(&self, score: u32) -> String {
+        let players = self.mode.players();
+
+        let inputs = run_length(self.frames.iter().map(|frame| {
+            frame.inputs[..players].iter().fold(String::new(), |hex, input| hex + &format!("{input:02x}"))
+        }));
+        let deltas = run_length(self.frames.iter().map(|frame| frame.delta.as_nanos().to_string()));
+
+        format!(
+            "{score}\nseed={}\nmode={}\nlevel={}\ninputs={inputs}\ndeltas={deltas}\n",
+            self.seed,
+            self.mode.name(),
+            self.level
+        )
+    }
+
+    /// Catches a record cut short or mangled on disk. Anyone editing the record can
+    /// compute it again, so only `verify` playing the run again says the score is real.
+    pub fn checksum(&self, score: u32) -> u64 {
+        checksum(self.body(score).as_bytes())
+    }
+
+    /// Contents of a record file, the score goes first.
+    pub fn to_record(&self, score: u32) -> String {
+        format!("{}checksum={:016x}\n", self.body(score), self.checksum(score))
+    }
+
+    pub fn parse_record(record: &str) -> Result<(u32, Self), VerifyError> {
+        let mut lines = record.lines();
+        let score = lines.next().and_then(|score| score.trim().parse().ok()).ok_or(VerifyError::Malformed("score"))?;
+
+        let mut field = |name: &'static str| {
+            lines
+                .next()
+                .and_then(|line| line.strip_prefix(name)?.strip_prefix('='))
+                .ok_or(VerifyError::Malformed(name))
+        };
+
+        let seed = field("seed")?.parse().map_err(|_| VerifyError::Malformed("seed"))?;
+        let mode = GameMode::from_name(field("mode")?).ok_or(VerifyError::Malformed("mode"))?;
+        let level = field("level")?
+            .parse()
+            .ok()
+            .filter(|level| (1..=MAX_LEVEL).contains(level))
+            .ok_or(VerifyError::Malformed("level"))?;
+        let inputs = expand(field("inputs")?, MAX_RUN_FRAMES).ok_or(VerifyError::Malformed("inputs"))?;
+        let deltas = expand(field("deltas")?, MAX_RUN_FRAMES).ok_or(VerifyError::Malformed("deltas"))?;
+        let claimed = u64::from_str_radix(field("checksum")?, 16).map_err(|_| VerifyError::Malformed("checksum"))?;
+
+        if inputs.len() != deltas.len() {
+            return Err(VerifyError::Malformed("inputs"));
+        }
+
+        let frames = inputs
+            .iter()
+            .zip(&deltas)
+            .map(|(inputs, delta)| Some(Frame {
+                delta: Duration::from_nanos(delta.parse().ok()?),
+                inputs: parse_inputs(inputs, mode.players())?
+            }))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(VerifyError::Malformed("inputs"))?;
+
+        let replay = Self { seed, mode, level, frames };
+
+        if replay.checksum(score) != claimed {
+            return Err(VerifyError::Checksum);
+        }
+
+        Ok((score, replay))
+    }
+}
+
+
+/// Packs what a chef pressed into a byte of the log.
+fn encode(input: ChefInput) -> u8 {
+    u8::from(input.direction < 0.)
+        | u8::from(input.direction > 0.) << 1
+        | u8::from(input.slice) << 2
+        | u8::from(input.bag) << 3
+        | u8::from(input.boost) << 4
+}
+
+fn decode(byte: u8) -> ChefInput {
+    ChefInput {
+        direction: f32::from(byte >> 1 & 1) - f32::from(byte & 1),
+        slice: byte & 1 << 2 != 0,
+        bag: byte & 1 << 3 != 0,
+        boost: byte & 1 << 4 != 0
+    }
+}
+
+fn parse_inputs(hex: &str, players: usize) -> Option<[u8; MAX_PLAYERS]> {
+    if hex.len() != players * 2 {
+        return None;
+    }
+
+    let mut inputs = [0; MAX_PLAYERS];
+    for (i, input) in inputs.iter_mut().take(players).enumerate() {
+        *input = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(inputs)
+}
+
+
+/// Comma separated values, repeats collapse into `count*value`.
+pub fn run_length(values: impl Iterator<Item = String>) -> String {
+    let mut runs: Vec<(usize, String)> = Vec::new();
+
+    for value in values {
+        match runs.last_mut() {
+            Some((count, last)) if *last == value => *count += 1,
+            _ => runs.push((1, value))
+        }
+    }
+
+    runs
+        .iter()
+        .map(|(count, value)| if *count == 1 { value.clone() } else { format!("{count}*{value}") })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Unpacks a `run_length` line, `None` when it's mangled or holds more than `limit` values.
+pub fn expand(line: &str, limit: usize) -> Option<Vec<String>> {
+    let mut values = Vec::new();
+
+    for run in line.split(',').filter(|run| !run.is_empty()) {
+        let (count, value) = match run.split_once('*') {
+            Some((count, value)) => (count.parse().ok()?, value),
+            None => (1, run)
+        };
+
+        // the counts come from files anyone can edit, check them before allocating
+        if values.len().checked_add(count)? > limit {
+            return None;
+        }
+        values.extend(std::iter::repeat_n(value.to_string(), count));
+    }
+
+    Some(values)
+}
+
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum VerifyError {
+    /// The record is missing or mangles the named field.
+    Malformed(&'static str),
+    Checksum,
+    /// Playing the log again ends with another score.
+    Score { claimed: u32, simulated: u32 }
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Malformed(field) => write!(f, "malformed record, bad {field}"),
+            Self::Checksum => write!(f, "checksum doesn't match the record"),
+            Self::Score { claimed, simulated } => write!(f, "record claims {claimed} but the run scores {simulated}")
+        }
+    }
+}
+
+
+/// Seed the next runs are played on, picked with `--seed`, the daily run or by retrying a run.
+/// Without one every run gets a fresh seed.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChosenSeed(pub Option<u64>);
+
+
+pub fn start_recording(
+    mut commands: Commands,
+    chosen: Res<ChosenSeed>,
+    mode: Res<GameMode>,
+    start: Res<StartLevel>,
+    playback: Option<Res<Playback>>
+) {
+    let seed = playback.map(|playback| playback.replay.seed).or(chosen.0);
+    let rng = seed.map_or_else(GameRng::from_time, GameRng::new);
+
+    commands.insert_resource(Replay::new(rng.seed(), *mode, start.0));
+    commands.insert_resource(rng);
+}
+
+
+/// A recorded run being watched, its inputs and frame times replace the player's.
+#[derive(Resource)]
+pub struct Playback {
+    replay: Replay,
+    next: usize
+}
+
+impl Playback {
+    pub const fn new(replay: Replay) -> Self {
+        Self { replay, next: 0 }
+    }
+}
+
+
+/// Skips the main menu, the time of the first frame is set before the run starts.
+fn start_playback(
+    playback: Res<Playback>,
+    mut strategy: ResMut<TimeUpdateStrategy>,
+    mut app_state: ResMut<NextState<AppState>>
+) {
+    if let Some(frame) = playback.replay.frames.first() {
+        *strategy = TimeUpdateStrategy::ManualDuration(frame.delta);
+        app_state.set(AppState::InGame);
+    }
+}
+
+
+fn play_back(
+    mut playback: ResMut<Playback>,
+    mut strategy: ResMut<TimeUpdateStrategy>,
+    mut chefs: Query<(&Player, &mut ChefInput)>
+) {
+    let Some(frame) = playback.replay.frames.get(playback.next).copied() else {
+        return;
+    };
+
+    for (player, mut input) in &mut chefs {
+        *input = decode(frame.inputs.get(player.id).copied().unwrap_or(0));
+    }
+
+    playback.next += 1;
+
+    // the player takes over if the log ends before the run does
+    *strategy = playback.replay.frames.get(playback.next).map_or(
+        TimeUpdateStrategy::Automatic,
+        |frame| TimeUpdateStrategy::ManualDuration(frame.delta)
+    );
+}
+
+
+fn stop_playback(mut commands: Commands, mut strategy: ResMut<TimeUpdateStrategy>) {
+    commands.remove_resource::<Playback>();
+    *strategy = TimeUpdateStrategy::Automatic;
+}
+
+
+/// `--record <file>` keeps the replay of every finished run, not just records.
+fn save_to_file(cli: Res<Cli>, session: Res<Session>, replay: Res<Replay>) {
+    let Some(path) = &cli.record else {
+        return;
+    };
+
+    if let Err(error) = fs::write(path, replay.to_record(session.score)) {
+        warn!("failed to save the replay to {}: {error}", path.display());
+    }
+}
+
+
+fn record_frame(time: Res<Time>, mut replay: ResMut<Replay>, chefs: Query<(&Player, &ChefInput)>) {
+    let mut inputs = [0; MAX_PLAYERS];
+    for (player, input) in &chefs {
+        if let Some(slot) = inputs.get_mut(player.id) {
+            *slot = encode(*input);
+        }
+    }
+
+    replay.frames.push(Frame { delta: time.delta(), inputs });
+}
+
+
+fn load_fruits(mut commands: Commands, asset_server: Res<AssetServer>, mut atlases: ResMut<Assets<TextureAtlas>>) {
+    commands.insert_resource(FruitTextures::new(&asset_server, &mut atlases));
+}
+
+
+/// Headless game with only the systems that decide the score, in the same
+/// `GameplaySet` steps as the game. Chefs get no input until something fills it.
+pub fn gameplay_app(mode: GameMode, seed: u64, level: u32) -> App {
+    let mut app = App::new();
+
+    app
+        .add_plugins((MinimalPlugins, AssetPlugin::default(), LevelPlugin))
+        .add_asset::<Image>()
+        .add_asset::<TextureAtlas>()
+        .add_state::<AppState>()
+        .insert_resource(Session::default())
+        .insert_resource(mode)
+        .insert_resource(StartLevel(level))
+        .insert_resource(SpawnTimer::at_level(level))
+        .insert_resource(GameRng::new(seed))
+        .add_event::<ChefHitEvent>()
+        .add_event::<SoundEvent>()
+        .add_event::<SplashEvent>()
+        .add_event::<TextEvent>()
+        .add_event::<SliceEvent>()
+        .add_event::<WaveEvent>()
+        .add_event::<BoostEvent>()
+        .add_event::<BoostUsedEvent>()
+        .add_systems(Startup, load_fruits)
+        .add_systems(Update, (
+            chef::update_level.in_set(GameplaySet::Spawn),
+            (spawn_fruits, increase_spawn_intensity).chain().in_set(GameplaySet::Spawn),
+            (chef::walk, chef::hit).chain().in_set(GameplaySet::Act),
+            fruit::hit.in_set(GameplaySet::Slice),
+            (spawn_boost, process_boost).chain().in_set(GameplaySet::Boost),
+            (fruit::fall, fruit::despawn_fallen_fruits).chain().in_set(GameplaySet::Fall),
+            send_garbage.in_set(GameplaySet::Garbage).run_if(resource_equals(GameMode::Versus))
+        ));
+
+    order_gameplay(&mut app);
+
+    for id in 0..mode.players() {
+        app.world.spawn((
+            Player::new(mode, id).at_level(level),
+            ChefInput::default(),
+            Transform::from_xyz(start_x(mode, id), FLOOR_Y, 1.),
+            TextureAtlasSprite::default(),
+            SpriteAnimation::new([Clip::new("idle", 0, 0, &[100])])
+        ));
+    }
+    app.world.resource_mut::<NextState<AppState>>().set(AppState::InGame);
+
+    app
+}
+
+
+#[derive(Resource, Default)]
+struct FrameInputs([u8; MAX_PLAYERS]);
+
+fn play_frame(frame: Res<FrameInputs>, mut chefs: Query<(&Player, &mut ChefInput)>) {
+    for (player, mut input) in &mut chefs {
+        *input = decode(frame.0.get(player.id).copied().unwrap_or(0));
+    }
+}
+
+
+/// Plays the log again and returns the score the run ends with.
+pub fn simulate(replay: &Replay) -> u32 {
+    let mut app = gameplay_app(replay.mode, replay.seed, replay.level);
+    app
+        .init_resource::<FrameInputs>()
+        .add_systems(Update, play_frame.in_set(GameplaySet::Input));
+
+    for frame in &replay.frames {
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(frame.delta));
+        app.insert_resource(FrameInputs(frame.inputs));
+        app.update();
+
+        if app.world.resource::<Session>().is_over() {
+            break;
+        }
+    }
+
+    app.world.resource::<Session>().score
+}
+
+
+/// Checks a record file and returns its score once a replay of the run confirms it.
+/// Every score read from a file or sent to the leaderboard goes through here.
+pub fn verify(record: &str) -> Result<u32, VerifyError> {
+    let (claimed, replay) = Replay::parse_record(record)?;
+    let simulated = simulate(&replay);
+
+    if simulated != claimed {
+        return Err(VerifyError::Score { claimed, simulated });
+    }
+
+    Ok(claimed)
+}
+
+
+/// Hard bots play until they lose or the frames run out, recorded like a real run.
+pub fn bot_run(mode: GameMode, seed: u64, level: u32, frames: usize) -> (u32, Replay) {
+    let mut app = gameplay_app(mode, seed, level);
+    app
+        .insert_resource(TimeUpdateStrategy::ManualDuration(BOT_FRAME))
+        .insert_resource(Replay::new(seed, mode, level))
+        .add_systems(Update, (drive_bot, record_frame).chain().in_set(GameplaySet::Input));
+
+    let chefs = app.world.query_filtered::<Entity, With<Player>>().iter(&app.world).collect::<Vec<_>>();
+    for chef in chefs {
+        app.world.entity_mut(chef).insert(Bot::new(Difficulty::Hard).seeded(seed));
+    }
+
+    for _ in 0..frames {
+        app.update();
+
+        if app.world.resource::<Session>().is_over() {
+            break;
+        }
+    }
+
+    (app.world.resource::<Session>().score, app.world.resource::<Replay>().clone())
+}
+
+
+pub fn read_replay(path: &Path) -> Result<(u32, Replay), String> {
+    let record = fs::read_to_string(path).map_err(|error| format!("can't read {}: {error}", path.display()))?;
+    Replay::parse_record(&record).map_err(|error| format!("{}: {error}", path.display()))
+}
+
+
+/// `verify [file]` on the command line, returns the exit code.
+pub fn verify_command(path: &Path) -> i32 {
+    let record = match fs::read_to_string(path) {
+        Ok(record) => record,
+        Err(error) => {
+            eprintln!("can't read {}: {error}", path.display());
+            return 2;
+        }
+    };
+
+    match verify(&record) {
+        Ok(score) => {
+            println!("{}: score {score} verified", path.display());
+            0
+        }
+        Err(error) => {
+            eprintln!("{}: {error}", path.display());
+            1
+        }
+    }
+}
+
+
+/// `--headless` plays `--replay` again, or lets bots play a run, and prints the score.
+pub fn run_headless(cli: &Cli) -> i32 {
+    let (score, replay) = if let Some(path) = &cli.replay {
+        match read_replay(path) {
+            Ok((_, replay)) => (simulate(&replay), replay),
+            Err(error) => {
+                eprintln!("{error}");
+                return 1;
+            }
+        }
+    } else {
+        let seed = cli.seed.unwrap_or_else(|| GameRng::from_time().seed());
+        bot_run(cli.mode.unwrap_or(GameMode::Solo), seed, cli.level.unwrap_or(1), BOT_RUN_FRAMES)
+    };
+
+    println!("seed {} mode {} score {score}", replay.seed, replay.mode.name());
+
+    if let Some(path) = &cli.record {
+        if let Err(error) = fs::write(path, replay.to_record(score)) {
+            eprintln!("can't write {}: {error}", path.display());
+            return 1;
+        }
+    }
+
+    0
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+
+    use crate::achievements::AchievementsPlugin;
+    use crate::animation::SpriteAnimationPlugin;
+    use crate::camera::CameraEffectsPlugin;
+    use crate::chef::bot::{Bot, Difficulty};
+    use crate::chef::Player;
+    use crate::cli::Cli;
+    use crate::fruits::sprite::EffectAtlases;
+    use crate::global::AppState;
+    use crate::particles::ParticlesPlugin;
+    use crate::settings::SettingsPlugin;
+    use crate::sound::SoundEvent;
+    use crate::states::session::{GameMode, Session, SessionPlugin};
+    use crate::stats::StatsPlugin;
+    use crate::utils::save::SaveData;
+
+    use super::{bot_run, expand, load_fruits, run_length, verify, ChosenSeed, Replay, ReplayPlugin, VerifyError, BOT_FRAME, MAX_RUN_FRAMES};
+
+    /// The game as the window runs it, every plugin that plays a part in a run
+    /// without rendering, audio or the save file.
+    fn game_app(seed: u64) -> App {
+        let mut app = App::new();
+
+        app
+            .add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::input::InputPlugin))
+            .add_asset::<Image>()
+            .add_asset::<TextureAtlas>()
+            .add_asset::<Font>()
+            .add_state::<AppState>()
+            .insert_resource(SaveData::default())
+            .insert_resource(Cli::default())
+            .insert_resource(ChosenSeed(Some(seed)))
+            .add_event::<SoundEvent>()
+            .add_plugins((
+                SessionPlugin,
+                ReplayPlugin,
+                CameraEffectsPlugin,
+                SettingsPlugin,
+                SpriteAnimationPlugin,
+                ParticlesPlugin,
+                StatsPlugin,
+                AchievementsPlugin
+            ));
+
+        // what the loading screen leaves behind
+        app.add_systems(Startup, load_fruits);
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let effects = EffectAtlases::new(&asset_server, &mut app.world.resource_mut::<Assets<TextureAtlas>>());
+        app.insert_resource(effects);
+
+        app
+    }
+
+    fn go_to(app: &mut App, state: AppState) {
+        app.world.resource_mut::<NextState<AppState>>().set(state);
+        app.update();
+    }
+
+    #[test]
+    fn test_it_compresses_repeated_values() {
+        let values = ["a", "a", "a", "b", "c", "c"].map(String::from);
+        let line = run_length(values.iter().cloned());
+
+        assert_eq!(line, "3*a,b,2*c");
+        assert_eq!(expand(&line, 6).unwrap(), values);
+        assert_eq!(expand("x*a", 6), None);
+        assert_eq!(expand(&line, 5), None);
+        assert_eq!(expand("18446744073709551615*00", MAX_RUN_FRAMES), None);
+    }
+
+    #[test]
+    fn test_it_refuses_oversized_records() {
+        let record = Replay::new(3, GameMode::Solo, 1).to_record(0);
+
+        let endless = record.replace("inputs=", &format!("inputs={}*00", MAX_RUN_FRAMES + 1));
+        assert_eq!(verify(&endless), Err(VerifyError::Malformed("inputs")));
+
+        let far = record.replace("level=1", "level=4294967295");
+        assert_eq!(verify(&far), Err(VerifyError::Malformed("level")));
+    }
+
+    #[test]
+    fn test_it_verifies_recorded_runs_and_catches_tampering() {
+        let (score, replay) = bot_run(GameMode::Coop, 7, 1, 1500);
+        assert!(score > 0);
+
+        let record = replay.to_record(score);
+        assert_eq!(verify(&record), Ok(score));
+
+        // a bumped score no longer matches the checksum of a file edited by hand
+        let edited = record.replacen(&score.to_string(), &(score + 10).to_string(), 1);
+        assert_eq!(verify(&edited), Err(VerifyError::Checksum));
+
+        // with the checksum fixed up the replay still gives it away
+        assert_eq!(verify(&replay.to_record(score + 10)), Err(VerifyError::Score { claimed: score + 10, simulated: score }));
+
+        assert_eq!(verify("120"), Err(VerifyError::Malformed("seed")));
+    }
+
+    #[test]
+    fn test_it_verifies_a_run_recorded_in_the_game() {
+        let mut app = game_app(11);
+        go_to(&mut app, AppState::MainMenu);
+        go_to(&mut app, AppState::InGame);
+
+        // a bot stands in for the player, on a clock slower than the wall clock
+        let chefs = app.world.query_filtered::<Entity, With<Player>>().iter(&app.world).collect::<Vec<_>>();
+        for chef in chefs {
+            app.world.entity_mut(chef).insert(Bot::new(Difficulty::Hard));
+        }
+        app.world.resource_mut::<Time>().set_relative_speed(0.8);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(BOT_FRAME));
+
+        for _ in 0..1500 {
+            app.update();
+        }
+
+        let score = app.world.resource::<Session>().score;
+        let replay = app.world.resource::<Replay>().clone();
+        assert!(score > 0);
+        assert_eq!(replay.seed, 11);
+        // slices froze the clock for a few frames
+        assert!(replay.frames.iter().any(|frame| frame.delta.is_zero()));
+
+        assert_eq!(verify(&replay.to_record(score)), Ok(score));
+    }
+}
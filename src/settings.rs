@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+
+use crate::utils::save::SaveData;
+
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreStartup, setup);
+    }
+}
+
+
+/// Effects the player can switch off, e.g. when sensitive to motion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Toggle {
+    ScreenShake,
+    HitStop,
+    ZoomPunch,
+    /// Races a see-through chef replaying the best run on the same seed.
+    Ghost,
+    /// Skips effects that cost the most on slow machines.
+    LowEffects
+}
+
+impl Toggle {
+    pub const ALL: [Self; 5] = [Self::ScreenShake, Self::HitStop, Self::ZoomPunch, Self::Ghost, Self::LowEffects];
+
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::ScreenShake => "settings.shake",
+            Self::HitStop => "settings.hitstop",
+            Self::ZoomPunch => "settings.zoom",
+            Self::Ghost => "settings.ghost",
+            Self::LowEffects => "settings.loweffects"
+        }
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::ScreenShake => "Screen shake",
+            Self::HitStop => "Hit-stop",
+            Self::ZoomPunch => "Zoom on level up",
+            Self::Ghost => "Ghost of best run",
+            Self::LowEffects => "Low effects"
+        }
+    }
+
+    const fn default(self) -> bool {
+        match self {
+            Self::ScreenShake | Self::HitStop | Self::ZoomPunch | Self::Ghost => true,
+            Self::LowEffects => false
+        }
+    }
+}
+
+
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    enabled: Vec<Toggle>
+}
+
+impl Settings {
+    pub fn load(save: &SaveData) -> Self {
+        let enabled = Toggle::ALL
+            .into_iter()
+            .filter(|toggle| save.get(toggle.key()).map_or_else(|| toggle.default(), |value| value == "on"))
+            .collect();
+
+        Self { enabled }
+    }
+
+    pub fn store(&self, save: &mut SaveData) {
+        for toggle in Toggle::ALL {
+            save.set(toggle.key(), if self.is_on(toggle) { "on" } else { "off" });
+        }
+    }
+
+    pub fn is_on(&self, toggle: Toggle) -> bool {
+        self.enabled.contains(&toggle)
+    }
+
+    pub fn flip(&mut self, toggle: Toggle) {
+        if self.is_on(toggle) {
+            self.enabled.retain(|enabled| *enabled != toggle);
+        } else {
+            self.enabled.push(toggle);
+        }
+    }
+}
+
+
+fn setup(mut commands: Commands, save: Res<SaveData>) {
+    commands.insert_resource(Settings::load(&save));
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::save::SaveData;
+    use super::{Settings, Toggle};
+
+    #[test]
+    fn test_it_stores_toggles() {
+        let mut save = SaveData::default();
+        let mut settings = Settings::load(&save);
+        assert!(settings.is_on(Toggle::ScreenShake));
+
+        settings.flip(Toggle::ScreenShake);
+        settings.store(&mut save);
+
+        let loaded = Settings::load(&save);
+        assert!(!loaded.is_on(Toggle::ScreenShake));
+        assert!(loaded.is_on(Toggle::HitStop));
+        assert!(!loaded.is_on(Toggle::LowEffects));
+        assert_eq!(save.get(Toggle::ScreenShake.key()), Some("off"));
+    }
+}
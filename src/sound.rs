@@ -1,8 +1,17 @@
 #![allow(non_camel_case_types)]
 
+use std::collections::HashMap;
+
 use bevy::prelude::*;
 use bevy_kira_audio::prelude::*;
+use crate::cli::Cli;
 use crate::fruits::fruit::FruitType;
+use crate::global::VIRTUAL_WIDTH;
+use crate::utils::random::uniform;
+use crate::utils::save::SaveData;
+
+
+const MAX_PAN: f64 = 0.4;
 
 
 pub struct SoundPlugin;
@@ -10,8 +19,12 @@ pub struct SoundPlugin;
 
 impl Plugin for SoundPlugin {
     fn build(&self, app: &mut App) {
-        app 
+        app
             .add_plugins(AudioPlugin)
+            .add_audio_channel::<MusicChannel>()
+            .add_audio_channel::<SfxChannel>()
+            .add_audio_channel::<UiChannel>()
+            .add_systems(Startup, (setup, apply_volumes))
             .add_systems(Update, spawn_sound)
             .add_event::<SoundEvent>()
         ;
@@ -19,7 +32,40 @@ impl Plugin for SoundPlugin {
 }
 
 
-#[derive(PartialEq, Eq, Debug)]
+/// Kira channels, each has its own volume.
+#[derive(Resource)]
+pub struct MusicChannel;
+
+#[derive(Resource)]
+pub struct SfxChannel;
+
+#[derive(Resource)]
+pub struct UiChannel;
+
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Channel {
+    Music,
+    Sfx,
+    Ui
+}
+
+impl Channel {
+    pub const fn volume_key(self) -> &'static str {
+        match self {
+            Self::Music => "volume.music",
+            Self::Sfx => "volume.sfx",
+            Self::Ui => "volume.ui"
+        }
+    }
+
+    pub fn volume(self, save: &SaveData) -> f64 {
+        save.get(self.volume_key()).and_then(|value| value.parse().ok()).unwrap_or(1.)
+    }
+}
+
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
 pub enum SoundType {
     SLASH,
     HIT,
@@ -28,64 +74,224 @@ pub enum SoundType {
     BOOST_HIT,
     GAME_OVER,
     RECORD,
+    ACHIEVEMENT,
+}
+
+impl SoundType {
+    pub const ALL: [Self; 8] = [
+        Self::SLASH,
+        Self::HIT,
+        Self::PENALTY,
+        Self::BOOST,
+        Self::BOOST_HIT,
+        Self::GAME_OVER,
+        Self::RECORD,
+        Self::ACHIEVEMENT
+    ];
+}
+
+
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+pub enum SoundKey {
+    Effect(SoundType),
+    Fruit(FruitType)
+}
+
+
+/// How a sound is played.
+#[derive(Debug, Clone, Copy)]
+pub struct SoundSpec {
+    pub file: &'static str,
+    pub volume: f64,
+    /// Playback rate is randomized within `1 ± pitch_variation`.
+    pub pitch_variation: f64,
+    /// New plays are dropped while this many are still playing.
+    pub max_voices: usize,
+    pub channel: Channel
+}
+
+const fn sfx(file: &'static str, volume: f64, pitch_variation: f64, max_voices: usize) -> SoundSpec {
+    SoundSpec { file, volume, pitch_variation, max_voices, channel: Channel::Sfx }
+}
+
+const fn ui(file: &'static str) -> SoundSpec {
+    SoundSpec { file, volume: 1.0, pitch_variation: 0., max_voices: 1, channel: Channel::Ui }
+}
+
+const fn stinger(file: &'static str) -> SoundSpec {
+    SoundSpec { file, volume: 1.0, pitch_variation: 0., max_voices: 1, channel: Channel::Music }
+}
+
+
+impl SoundKey {
+    pub const fn spec(&self) -> SoundSpec {
+        match self {
+            Self::Effect(sound_type) => match sound_type {
+                SoundType::SLASH => sfx("audio/slash.wav", 0.5, 0.1, 4),
+                SoundType::HIT => sfx("audio/hit.wav", 0.7, 0.1, 4),
+                SoundType::PENALTY => sfx("audio/penalty.wav", 1.0, 0.05, 3),
+                SoundType::BOOST => sfx("audio/boost.wav", 1.0, 0., 1),
+                SoundType::BOOST_HIT => sfx("audio/critical.wav", 1.0, 0.15, 4),
+                SoundType::GAME_OVER => ui("audio/game-over.wav"),
+                SoundType::RECORD => stinger("audio/record.wav"),
+                SoundType::ACHIEVEMENT => ui("audio/record.wav")
+            },
+            Self::Fruit(fruit) => match fruit {
+                FruitType::APPLE => sfx("audio/apple-slice.wav", 1.0, 0.1, 3),
+                FruitType::ORANGE => sfx("audio/orange-slice.wav", 1.0, 0.1, 3),
+                FruitType::STRAWBERRY => sfx("audio/strawberry-slice.wav", 1.0, 0.1, 3),
+                FruitType::PINEAPPLE  => sfx("audio/pineapple.wav", 1.0, 0.05, 2),
+                FruitType::WATERMELON => sfx("audio/watermelon.wav", 1.0, 0.1, 3),
+                FruitType::BANANA => sfx("audio/banana.wav", 1.0, 0.1, 3),
+                FruitType::POME => sfx("audio/pome.wav", 1.0, 0.05, 2)
+            }
+        }
+    }
 }
 
 
 #[derive(Event, Debug)]
 pub struct SoundEvent {
-    pub sound: &'static str,
+    pub sound: SoundKey,
+    /// World position of the source, positioned sounds are panned by x.
+    pub position: Option<Vec2>,
 }
 
 impl SoundEvent {
     pub const fn sound(sound_type: SoundType) -> Self {
-        let file = match sound_type {
-            SoundType::SLASH => "audio/slash.wav",
-            SoundType::HIT => "audio/hit.wav",
-            SoundType::PENALTY => "audio/penalty.wav",
-            SoundType::BOOST => "audio/boost.wav",
-            SoundType::BOOST_HIT => "audio/critical.wav",
-            SoundType::GAME_OVER => "audio/game-over.wav",
-            SoundType::RECORD => "audio/record.wav"
-        };
-
-        Self {sound: file}
-    } 
+        Self { sound: SoundKey::Effect(sound_type), position: None }
+    }
 
     pub const fn fruit_sound(fruit: FruitType) -> Self {
-        let file = match fruit {
-            FruitType::APPLE => "audio/apple-slice.wav",
-            FruitType::ORANGE => "audio/orange-slice.wav",
-            FruitType::STRAWBERRY => "audio/strawberry-slice.wav",
-            FruitType::PINEAPPLE  => "audio/pineapple.wav",
-            FruitType::WATERMELON => "audio/watermelon.wav",
-            FruitType::BANANA => "audio/banana.wav",
-            FruitType::POME => "audio/pome.wav"
-        };
+        Self { sound: SoundKey::Fruit(fruit), position: None }
+    }
+
+    pub const fn at(mut self, position: Vec2) -> Self {
+        self.position = Some(position);
+        self
+    }
+}
+
+
+/// Kira panning for a world position: `0.0` is hard left, `0.5` center, `1.0` hard right.
+/// Never pans fully to one side so both speakers still carry the sound.
+pub fn panning(position: Option<Vec2>) -> f64 {
+    position.map_or(0.5, |position| {
+        let x = f64::from((position.x / (VIRTUAL_WIDTH / 2.)).clamp(-1., 1.));
+        0.5 + x * MAX_PAN
+    })
+}
+
+
+struct RegisteredSound {
+    handle: Handle<bevy_kira_audio::AudioSource>,
+    spec: SoundSpec,
+    voices: Vec<Handle<AudioInstance>>
+}
+
+
+/// Preloaded handles and playback settings for every sound in the game.
+#[derive(Resource)]
+pub struct SoundRegistry {
+    sounds: HashMap<SoundKey, RegisteredSound>
+}
+
+impl SoundRegistry {
+    pub fn new(asset_server: &AssetServer) -> Self {
+        let keys = SoundType::ALL
+            .into_iter()
+            .map(SoundKey::Effect)
+            .chain(FruitType::ALL.into_iter().map(SoundKey::Fruit));
 
-        Self {sound: file}
+        let sounds = keys
+            .map(|key| {
+                let spec = key.spec();
+                let sound = RegisteredSound { handle: asset_server.load(spec.file), spec, voices: Vec::new() };
+                (key, sound)
+            })
+            .collect();
+
+        Self { sounds }
     }
 }
 
 
-#[derive(Component)]
-struct Sound;
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(SoundRegistry::new(&asset_server));
+}
+
+
+pub fn apply_volumes(
+    save: Res<SaveData>,
+    cli: Res<Cli>,
+    music: Res<AudioChannel<MusicChannel>>,
+    sfx: Res<AudioChannel<SfxChannel>>,
+    ui: Res<AudioChannel<UiChannel>>
+) {
+    let volume = |channel: Channel| if cli.mute { 0. } else { channel.volume(&save) };
+
+    music.set_volume(volume(Channel::Music));
+    sfx.set_volume(volume(Channel::Sfx));
+    ui.set_volume(volume(Channel::Ui));
+}
+
+
+fn play<T: Resource>(channel: &AudioChannel<T>, sound: &mut RegisteredSound, panning: f64) {
+    sound.voices.retain(|voice| !matches!(channel.state(voice), PlaybackState::Stopped));
+
+    if sound.voices.len() >= sound.spec.max_voices {
+        return;
+    }
+
+    let variation = sound.spec.pitch_variation;
+    let rate = if variation > 0. { 1. + uniform(-variation, variation) } else { 1. };
+
+    let voice = channel
+        .play(sound.handle.clone())
+        .with_volume(sound.spec.volume)
+        .with_playback_rate(rate)
+        .with_panning(panning)
+        .handle();
+
+    sound.voices.push(voice);
+}
 
 
 fn spawn_sound(
-    asset_server: Res<AssetServer>, 
-    audio: Res<Audio>,
+    mut registry: ResMut<SoundRegistry>,
+    music: Res<AudioChannel<MusicChannel>>,
+    sfx: Res<AudioChannel<SfxChannel>>,
+    ui: Res<AudioChannel<UiChannel>>,
     mut events: EventReader<SoundEvent>
 ) {
     for event in &mut events {
+        let Some(sound) = registry.sounds.get_mut(&event.sound) else {
+            continue;
+        };
 
-        let handle = asset_server.load(event.sound);
+        let panning = panning(event.position);
 
-        let volume = match event.sound {
-            "audio/slash.wav" => 0.5,
-            "audio/hit.wav"   => 0.7,
-            _                 => 1.0
-        };
+        match sound.spec.channel {
+            Channel::Music => play(&music, sound, panning),
+            Channel::Sfx => play(&sfx, sound, panning),
+            Channel::Ui => play(&ui, sound, panning)
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Vec2;
+    use super::{panning, MAX_PAN};
 
-        audio.play(handle).with_volume(volume);
+    #[test]
+    fn test_it_pans_by_x() {
+        assert!((panning(None) - 0.5).abs() < f64::EPSILON);
+        assert!((panning(Some(Vec2::new(0., -200.))) - 0.5).abs() < f64::EPSILON);
+        assert!(panning(Some(Vec2::new(-300., 0.))) < 0.5);
+        assert!(panning(Some(Vec2::new(300., 0.))) > 0.5);
+        assert!((panning(Some(Vec2::new(5000., 0.))) - (0.5 + MAX_PAN)).abs() < f64::EPSILON);
     }
 }
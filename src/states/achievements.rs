@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use crate::achievements::ACHIEVEMENTS;
+use crate::global::{AppState, SCREEN_SCALE};
+use crate::utils::save::SaveData;
+use crate::utils::ui::text;
+
+
+pub struct AchievementsScreenPlugin;
+
+impl Plugin for AchievementsScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter::<AppState>(AppState::Achievements), setup)
+            .add_systems(OnExit::<AppState>(AppState::Achievements), exit)
+            .add_systems(Update, back.run_if(in_state(AppState::Achievements)))
+        ;
+    }
+}
+
+
+#[derive(Component)]
+struct AchievementsItem;
+
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, save: Res<SaveData>) {
+    commands.spawn((SpriteBundle {
+        texture: asset_server.load("images/menu.png"),
+        transform: Transform::from_xyz(0., 0., 10.).with_scale(Vec3::splat(SCREEN_SCALE)),
+        ..default()
+    }, AchievementsItem));
+
+    let title = text(&asset_server, "Achievements", 0., 200., 60.);
+    commands.spawn((title, AchievementsItem));
+
+    for (i, achievement) in ACHIEVEMENTS.iter().enumerate() {
+        let mark = if achievement.is_unlocked(&save) { "[x]" } else { "[ ]" };
+
+        let progress = match achievement.progress(&save) {
+            Some(value) if !achievement.is_unlocked(&save) => format!(" ({value}/{})", achievement.target),
+            _ => String::new()
+        };
+
+        let line = format!("{mark} {}: {}{progress}", achievement.name, achievement.description);
+        let line = text(&asset_server, &line, 0., 130. - 40. * i as f32, 28.);
+        commands.spawn((line, AchievementsItem));
+    }
+
+    let hint = text(&asset_server, "Press --Esc-- to go back", 0., -200., 28.);
+    commands.spawn((hint, AchievementsItem));
+}
+
+
+fn exit(mut commands: Commands, query: Query<Entity, With<AchievementsItem>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+
+fn back(keys: Res<Input<KeyCode>>, mut app_state: ResMut<NextState<AppState>>) {
+    if keys.just_pressed(KeyCode::Escape) {
+        app_state.set(AppState::MainMenu);
+    }
+}
@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+
+use crate::chef::Player;
+use crate::chef::bot::{Bot, Difficulty};
+use crate::components::Clock;
+use crate::fruits::fruit::Fruit;
+use crate::global::{AppState, VIRTUAL_HEIGHT, VIRTUAL_WIDTH};
+use crate::utils::ui::text;
+
+use super::session::{GameMode, Session};
+
+/// Seconds without input on the main menu before the demo starts.
+const IDLE_SECONDS: f32 = 15.;
+
+
+pub struct DemoPlugin;
+
+impl Plugin for DemoPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter::<AppState>(AppState::MainMenu), reset_idle)
+            .add_systems(Update, count_idle.run_if(in_state(AppState::MainMenu)))
+            .add_systems(OnEnter::<AppState>(AppState::Demo), setup)
+            .add_systems(OnExit::<AppState>(AppState::Demo), exit)
+            .add_systems(Update, (leave, game_over).run_if(in_state(AppState::Demo)))
+        ;
+    }
+}
+
+
+#[derive(Resource)]
+struct IdleClock(Clock);
+
+#[derive(Component)]
+struct DemoItem;
+
+
+fn any_input(keys: &Input<KeyCode>, buttons: &Input<GamepadButton>, mouse: &Input<MouseButton>) -> bool {
+    keys.get_pressed().next().is_some()
+        || buttons.get_pressed().next().is_some()
+        || mouse.get_pressed().next().is_some()
+}
+
+
+fn reset_idle(mut commands: Commands) {
+    commands.insert_resource(IdleClock(Clock::seconds_once(IDLE_SECONDS)));
+}
+
+
+fn count_idle(
+    time: Res<Time>,
+    keys: Res<Input<KeyCode>>,
+    buttons: Res<Input<GamepadButton>>,
+    mouse: Res<Input<MouseButton>>,
+    mut idle: ResMut<IdleClock>,
+    mut app_state: ResMut<NextState<AppState>>
+) {
+    if any_input(&keys, &buttons, &mouse) {
+        idle.0 = Clock::seconds_once(IDLE_SECONDS);
+        return;
+    }
+
+    idle.0.tick(time.delta());
+
+    if idle.0.finished() {
+        app_state.set(AppState::Demo);
+    }
+}
+
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut session: ResMut<Session>,
+    mut mode: ResMut<GameMode>,
+    chefs: Query<(&Player, Entity)>
+) {
+    *session = Session::default();
+    mode.set_if_neq(GameMode::Solo);
+
+    for (player, entity) in &chefs {
+        if player.id == 0 {
+            // a different chef every time, from clumsy to flawless
+            commands.entity(entity).insert(Bot::new(Difficulty::random()));
+        }
+    }
+
+    // darken the game so the demo doesn't pass for a real one
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgba(0., 0., 0., 0.45),
+                custom_size: Some(Vec2::new(VIRTUAL_WIDTH, VIRTUAL_HEIGHT)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0., 0., 15.),
+            ..default()
+        },
+        DemoItem
+    ));
+
+    let mut title = text(&asset_server, "Demo", 0., 40., 70.);
+    title.transform.translation.z = 16.;
+    commands.spawn((title, DemoItem));
+
+    let mut hint = text(&asset_server, "Press any key", 0., -20., 36.);
+    hint.transform.translation.z = 16.;
+    commands.spawn((hint, DemoItem));
+}
+
+
+fn exit(
+    mut commands: Commands,
+    mut session: ResMut<Session>,
+    items: Query<Entity, With<DemoItem>>,
+    fruits: Query<Entity, With<Fruit>>,
+    bots: Query<Entity, With<Bot>>
+) {
+    for entity in items.iter().chain(&fruits) {
+        commands.entity(entity).despawn();
+    }
+
+    for entity in &bots {
+        commands.entity(entity).remove::<Bot>();
+    }
+
+    *session = Session::default();
+}
+
+
+fn leave(
+    keys: Res<Input<KeyCode>>,
+    buttons: Res<Input<GamepadButton>>,
+    mouse: Res<Input<MouseButton>>,
+    mut app_state: ResMut<NextState<AppState>>
+) {
+    if any_input(&keys, &buttons, &mouse) {
+        app_state.set(AppState::MainMenu);
+    }
+}
+
+
+/// The demo never reaches the finish screen, so no record or stat is written.
+fn game_over(session: Res<Session>, mut app_state: ResMut<NextState<AppState>>) {
+    if session.is_over() {
+        app_state.set(AppState::MainMenu);
+    }
+}
@@ -1,12 +1,17 @@
 use bevy::prelude::*;
 
-use crate::components::Clock;
-use crate::global::AppState;
+use crate::particles::{spawn_tagged_emitter, EmitterSpec};
+use crate::global::{AppState, SCREEN_SCALE, VIRTUAL_HEIGHT, VIRTUAL_WIDTH};
 use crate::utils::ui::text;
 use crate::utils::record;
+use crate::utils::save::SaveData;
+use crate::chef::skins::unlock_cosmetics;
+use crate::fruits::fruit::FruitType;
+use crate::stats::{format_time, RunStats};
+use crate::replay::{ChosenSeed, Replay};
 
 use crate::sound::{SoundEvent, SoundType};
-use super::session::Session;
+use super::session::{GameMode, Session};
 
 
 pub struct FinishPlugin;
@@ -16,7 +21,7 @@ impl Plugin for FinishPlugin {
         app
             .add_systems(OnEnter::<AppState>(AppState::Finish), setup)
             .add_systems(OnExit::<AppState>(AppState::Finish), exit)
-            .add_systems(Update, (restart, animate_confetti).run_if(in_state(AppState::Finish)))
+            .add_systems(Update, restart.run_if(in_state(AppState::Finish)))
         ;
     }
 }
@@ -25,18 +30,41 @@ impl Plugin for FinishPlugin {
 #[derive(Component)]
 struct FinishItem;
 
-#[derive(Component)]
-struct Confetti;
+const CONFETTI: EmitterSpec = EmitterSpec {
+    rate: 40.,
+    duration: 3.,
+    lifetime: (3.5, 5.),
+    speed: (80., 200.),
+    direction: -std::f32::consts::FRAC_PI_2,
+    spread: 0.5,
+    gravity: -60.,
+    size: (10., 8.),
+    area: Vec2::new(VIRTUAL_WIDTH / 2., 0.),
+    ..EmitterSpec::DEFAULT
+};
+
+const CONFETTI_COLORS: [Color; 4] = [
+    Color::rgb(0.97, 0.21, 0.13),
+    Color::rgb(1., 0.9, 0.16),
+    Color::rgb(0.3, 0.75, 0.95),
+    Color::rgb(0.55, 0.9, 0.3)
+];
 
 
 fn setup(
     mut commands: Commands, 
     asset_server: Res<AssetServer>, 
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>, 
     session: Res<Session>,
+    mode: Res<GameMode>,
+    run: Res<RunStats>,
+    replay: Res<Replay>,
+    mut save: ResMut<SaveData>,
     mut sound: EventWriter<SoundEvent>
 ) {
-    if record::is_record(session.score) {
+    // a versus game is two half games, it doesn't compete with solo records
+    let is_record = *mode != GameMode::Versus && record::is_record(session.score);
+
+    if is_record {
         // new record text
         {
             let text_style = TextStyle {
@@ -57,7 +85,7 @@ fn setup(
         // record menu image
         commands.spawn((SpriteBundle {
             texture: asset_server.load("images/menu-record.png"),
-            transform: Transform::from_xyz(0., 0., 10.).with_scale(Vec3::splat(7.5)),
+            transform: Transform::from_xyz(0., 0., 10.).with_scale(Vec3::splat(SCREEN_SCALE)),
             ..default()
         }, FinishItem));    
 
@@ -66,7 +94,7 @@ fn setup(
         // add background image
         commands.spawn((SpriteBundle {
             texture: asset_server.load("images/menu.png"),
-            transform: Transform::from_xyz(0., 0., 10.).with_scale(Vec3::splat(7.5)),
+            transform: Transform::from_xyz(0., 0., 10.).with_scale(Vec3::splat(SCREEN_SCALE)),
             ..default()
         }, FinishItem));
 
@@ -75,39 +103,69 @@ fn setup(
 
     // text
     {
-        let title = text(&asset_server, "End Game!", 0., 100., 70.);
+        let title = match (*mode, session.winner()) {
+            (GameMode::Versus, Some(winner)) => format!("P{} wins!", winner + 1),
+            (GameMode::Versus, None) => "Draw!".to_string(),
+            _ => "End Game!".to_string()
+        };
+        let title = text(&asset_server, &title, 0., 100., 70.);
         commands.spawn((title, FinishItem));
 
-        let score = text(&asset_server, format!("Your score {}", session.score).as_str(), 0., 20., 40.);
+        let score = match *mode {
+            GameMode::Solo => format!("Your score {}", session.score),
+            GameMode::Coop => {
+                let [first, second] = session.player_scores;
+                format!("Team score {} (P1 {first} / P2 {second})", session.score)
+            }
+            GameMode::Versus => {
+                let [first, second] = session.player_scores;
+                format!("P1 {first} : {second} P2")
+            }
+        };
+        let score = text(&asset_server, &score, 0., 20., 40.);
         commands.spawn((score, FinishItem));
         
         let enter_game_text = text(&asset_server, "Press --Enter-- to restart!", 0., -20., 40.);
         commands.spawn((enter_game_text, FinishItem));        
-    }
 
-    // create confetti
-    if record::is_record(session.score) {
-        let transform = Transform::from_xyz(0., 800., 11.).with_scale(Vec3::splat(7.5));
-
-        let texture = TextureAtlas::from_grid(
-            asset_server.load("images/confetti.png"),
-            Vec2::new(152., 200.),
-            2,
-            1,
-            None,
-            None
-        );
+        let retry_text = text(&asset_server, "--R-- play the same fruits again", 0., -52., 24.);
+        commands.spawn((retry_text, FinishItem));
 
-        let texture_atlas = texture_atlases.add(texture);
+        let unlocked = unlock_cosmetics(&mut save, session.score);
+        if !unlocked.is_empty() {
+            let unlocked_text = format!("Unlocked: {}", unlocked.join(", "));
+            let unlocked_text = text(&asset_server, &unlocked_text, 0., -80., 32.);
+            commands.spawn((unlocked_text, FinishItem));
+        }
 
-        let confetti = SpriteSheetBundle { 
-            texture_atlas, 
-            transform, 
-            ..default()
-        };
+        // run breakdown
+        let sliced = FruitType::ALL
+            .iter()
+            .filter_map(|fruit| run.sliced.get(fruit).map(|count| format!("{} {count}", fruit.name())))
+            .collect::<Vec<_>>()
+            .join("  ");
+        let sliced = text(&asset_server, &format!("Sliced {}: {sliced}", run.total_sliced()), 0., -130., 28.);
+        commands.spawn((sliced, FinishItem));
+
+        let summary = format!(
+            "Missed {}  Boosts {}  Best combo {}  Time {}",
+            run.missed, run.boosts_used, run.longest_combo, format_time(run.seconds as u32)
+        );
+        let summary = text(&asset_server, &summary, 0., -165., 28.);
+        commands.spawn((summary, FinishItem));
+    }
 
-        commands.spawn((confetti, Confetti, Clock::seconds(0.3), FinishItem));
-        record::write_record(session.score);
+    // create confetti
+    if is_record {
+        for color in CONFETTI_COLORS {
+            let position = Vec3::new(0., VIRTUAL_HEIGHT / 2. + 10., 12.);
+            // confetti still falling goes away with the screen on a quick restart
+            spawn_tagged_emitter(&mut commands, EmitterSpec { color: (color, color.with_a(0.6)), ..CONFETTI }, position, |entity| {
+                entity.insert(FinishItem);
+            });
+        }
+
+        record::write_record(session.score, &replay);
     }
 }
 
@@ -119,29 +177,20 @@ fn exit(mut commands: Commands, query: Query<Entity, With<FinishItem>>) {
 }
 
 
-fn restart(keys: Res<Input<KeyCode>>, mut app_state: ResMut<NextState<AppState>>, mut session: ResMut<Session>) {
-    if keys.just_pressed(KeyCode::Return) {
-        *session = Session::default();
-        app_state.set(AppState::InGame);
-    }
-}
-
-fn animate_confetti(
-    time: Res<Time>,
-    mut query: Query<(&mut Clock, &mut TextureAtlasSprite, &mut Transform, Entity), With<Confetti>>,
-    mut commands: Commands
+fn restart(
+    keys: Res<Input<KeyCode>>,
+    replay: Res<Replay>,
+    mut chosen: ResMut<ChosenSeed>,
+    mut app_state: ResMut<NextState<AppState>>,
+    mut session: ResMut<Session>
 ) {
-    for (mut clock, mut sprite, mut transform, entity) in &mut query {
-        clock.tick(time.delta());
-        
-        if clock.finished() {
-            sprite.index = if sprite.index == 0 { 1 } else { 0 };
-        }
-
-        transform.translation.y -= 300. * time.delta_seconds(); 
+    if keys.just_pressed(KeyCode::R) {
+        // keeps playing this seed until the player goes back to the menu
+        chosen.set_if_neq(ChosenSeed(Some(replay.seed)));
+    }
 
-        if transform.translation.y < -1700. {
-            commands.entity(entity).despawn();
-        }
+    if keys.just_pressed(KeyCode::Return) || keys.just_pressed(KeyCode::R) {
+        *session = Session::default();
+        app_state.set(AppState::InGame);
     }
 }
@@ -0,0 +1,181 @@
+use std::io;
+
+use bevy::prelude::*;
+
+use crate::global::{AppState, SCREEN_SCALE};
+use crate::leaderboard::{fetch_top, Entry, Leaderboard, Pending, TOP_SCORES};
+use crate::utils::record;
+use crate::utils::ui::text;
+
+
+pub struct LeaderboardScreenPlugin;
+
+impl Plugin for LeaderboardScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter::<AppState>(AppState::Leaderboard), setup)
+            .add_systems(OnExit::<AppState>(AppState::Leaderboard), exit)
+            .add_systems(Update, (show_scores, back).run_if(in_state(AppState::Leaderboard)))
+        ;
+    }
+}
+
+
+#[derive(Component)]
+struct LeaderboardItem;
+
+#[derive(Component)]
+struct Fetching(Pending<io::Result<Vec<Entry>>>);
+
+#[derive(Component)]
+struct StatusText;
+
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, leaderboard: Res<Leaderboard>) {
+    commands.spawn((SpriteBundle {
+        texture: asset_server.load("images/menu.png"),
+        transform: Transform::from_xyz(0., 0., 10.).with_scale(Vec3::splat(SCREEN_SCALE)),
+        ..default()
+    }, LeaderboardItem));
+
+    let title = text(&asset_server, "Leaderboard", 0., 220., 60.);
+    commands.spawn((title, LeaderboardItem));
+
+    let hint = text(&asset_server, "Press --Esc-- to go back", 0., -230., 28.);
+    commands.spawn((hint, LeaderboardItem));
+
+    let Some(endpoint) = leaderboard.endpoint.clone() else {
+        let status = text(&asset_server, &offline(), 0., 150., 30.);
+        commands.spawn((status, LeaderboardItem));
+        return;
+    };
+
+    let status = text(&asset_server, "Loading...", 0., 150., 30.);
+    let fetching = Fetching(Pending::spawn(move || fetch_top(&endpoint, TOP_SCORES)));
+    commands.spawn((status, fetching, StatusText, LeaderboardItem));
+}
+
+
+/// Without the global board the local record is all there is to show.
+fn offline() -> String {
+    format!("Offline, your best score is {}", record::read_file())
+}
+
+
+fn exit(mut commands: Commands, query: Query<Entity, With<LeaderboardItem>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+
+fn show_scores(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut query: Query<(&Fetching, &mut Text, Entity), With<StatusText>>
+) {
+    for (fetching, mut status, entity) in &mut query {
+        let Some(result) = fetching.0.take() else {
+            continue;
+        };
+        commands.entity(entity).remove::<Fetching>();
+
+        let entries = match result {
+            Ok(entries) if !entries.is_empty() => entries,
+            Ok(_) => {
+                status.sections[0].value = "No scores yet, be the first!".to_string();
+                continue;
+            }
+            Err(error) => {
+                warn!("failed to fetch leaderboard: {error}");
+                status.sections[0].value = offline();
+                continue;
+            }
+        };
+
+        status.sections[0].value = "Top scores".to_string();
+
+        for (i, entry) in entries.iter().enumerate() {
+            let line = format!("{:>2}. {:>5}  {}", i + 1, entry.score, entry.mode);
+            let line = text(&asset_server, &line, 0., 110. - 32. * i as f32, 28.);
+            commands.spawn((line, LeaderboardItem));
+        }
+    }
+}
+
+
+fn back(keys: Res<Input<KeyCode>>, mut app_state: ResMut<NextState<AppState>>) {
+    if keys.just_pressed(KeyCode::Escape) {
+        app_state.set(AppState::MainMenu);
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+
+    use crate::global::AppState;
+    use crate::leaderboard::stub::stub_server;
+    use crate::leaderboard::{Endpoint, Leaderboard};
+    use super::{Fetching, LeaderboardScreenPlugin};
+
+    fn screen_app(endpoint: Option<Endpoint>) -> App {
+        let mut app = App::new();
+        app
+            .add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::input::InputPlugin))
+            .add_asset::<Image>()
+            .add_asset::<Font>()
+            .add_state::<AppState>()
+            .insert_resource(Leaderboard { endpoint })
+            .add_plugins(LeaderboardScreenPlugin);
+
+        app.world.resource_mut::<NextState<AppState>>().set(AppState::Leaderboard);
+        app.update();
+        app
+    }
+
+    /// Runs frames until the scores arrived, then returns every text on the screen.
+    fn shown_texts(app: &mut App) -> Vec<String> {
+        for _ in 0..1000 {
+            if app.world.query::<&Fetching>().iter(&app.world).count() == 0 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(5));
+            app.update();
+        }
+        app.update();
+
+        app.world.query::<&Text>().iter(&app.world).map(|text| text.sections[0].value.clone()).collect()
+    }
+
+    #[test]
+    fn test_it_shows_the_top_scores() {
+        let (endpoint, server) = stub_server("200 OK", "120 solo\n95 versus\n", 1);
+        let texts = shown_texts(&mut screen_app(Some(endpoint)));
+
+        assert!(texts.contains(&"Top scores".to_string()));
+        assert!(texts.contains(&" 1.   120  solo".to_string()));
+        assert!(texts.contains(&" 2.    95  versus".to_string()));
+        assert!(server.join().unwrap()[0].starts_with("GET /scores?limit=10 HTTP/1.0"));
+    }
+
+    #[test]
+    fn test_it_falls_back_to_the_local_record() {
+        let offline = |texts: &[String]| texts.iter().any(|text| text.starts_with("Offline, your best score is"));
+
+        // no leaderboard url saved
+        assert!(offline(&shown_texts(&mut screen_app(None))));
+
+        let (endpoint, server) = stub_server("500 Internal Server Error", "", 1);
+        let texts = shown_texts(&mut screen_app(Some(endpoint)));
+        server.join().unwrap();
+
+        assert!(offline(&texts));
+        assert!(!texts.contains(&"Loading...".to_string()));
+    }
+}
@@ -0,0 +1,130 @@
+use bevy::prelude::*;
+
+use crate::assets::{PreloadedAssets, Progress};
+use crate::fruits::sprite::{EffectAtlases, FruitTextures};
+use crate::global::AppState;
+
+const BAR_WIDTH: f32 = 480.;
+const BAR_HEIGHT: f32 = 24.;
+
+
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter::<AppState>(AppState::Loading), setup)
+            .add_systems(OnExit::<AppState>(AppState::Loading), exit)
+            .add_systems(Update, check_progress.run_if(in_state(AppState::Loading)))
+        ;
+    }
+}
+
+
+#[derive(Component)]
+struct LoadingItem;
+
+#[derive(Component)]
+struct ProgressBar;
+
+#[derive(Component)]
+struct StatusText;
+
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(PreloadedAssets::load(&asset_server));
+
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/mn-regular.otf"),
+        font_size: 32.,
+        color: Color::WHITE
+    };
+
+    let root = NodeBundle {
+        style: Style {
+            width: Val::Percent(100.),
+            height: Val::Percent(100.),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(20.),
+            ..default()
+        },
+        background_color: Color::BLACK.into(),
+        z_index: ZIndex::Global(100),
+        ..default()
+    };
+
+    let bar = NodeBundle {
+        style: Style {
+            width: Val::Px(BAR_WIDTH),
+            height: Val::Px(BAR_HEIGHT),
+            border: UiRect::all(Val::Px(3.)),
+            ..default()
+        },
+        border_color: Color::WHITE.into(),
+        ..default()
+    };
+
+    let fill = NodeBundle {
+        style: Style {
+            width: Val::Percent(0.),
+            height: Val::Percent(100.),
+            ..default()
+        },
+        background_color: Color::rgb_u8(255, 230, 41).into(),
+        ..default()
+    };
+
+    commands.spawn((root, LoadingItem)).with_children(|root| {
+        root.spawn((TextBundle::from_section("Loading...", text_style), StatusText));
+        root.spawn(bar).with_children(|bar| {
+            bar.spawn((fill, ProgressBar));
+        });
+    });
+}
+
+
+fn exit(mut commands: Commands, query: Query<Entity, With<LoadingItem>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+
+fn check_progress(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    preloaded: Res<PreloadedAssets>,
+    mut bar: Query<(&mut Style, &mut BackgroundColor), With<ProgressBar>>,
+    mut status: Query<&mut Text, With<StatusText>>,
+    mut app_state: ResMut<NextState<AppState>>
+) {
+    match preloaded.progress(&asset_server) {
+        Progress::Loading(share) => {
+            for (mut style, _) in &mut bar {
+                style.width = Val::Percent(share * 100.);
+            }
+        }
+        Progress::Done => {
+            commands.insert_resource(FruitTextures::new(&asset_server, &mut texture_atlases));
+            commands.insert_resource(EffectAtlases::new(&asset_server, &mut texture_atlases));
+            app_state.set(AppState::MainMenu);
+        }
+        Progress::Failed(files) => {
+            for (mut style, mut color) in &mut bar {
+                style.width = Val::Percent(100.);
+                *color = Color::rgb_u8(247, 54, 32).into();
+            }
+
+            let message = format!("Could not load game files:\n{}\n\nPlease reinstall the game.", files.join("\n"));
+
+            for mut text in &mut status {
+                if text.sections[0].value != message {
+                    text.sections[0].value.clone_from(&message);
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 
+use crate::cli::Cli;
 use crate::global::AppState;
+use crate::replay::ChosenSeed;
+use crate::utils::random::daily_seed;
+use crate::states::session::GameMode;
 use crate::utils::ui::text;
 
 
@@ -46,6 +50,18 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
 
     let enter_game_text = text(&asset_server, "Press --Enter-- to start!", 0., 10., 40.);
     commands.spawn((enter_game_text, MenuItem));
+
+    let wardrobe_text = text(&asset_server, "--W-- wardrobe  --Q-- achievements  --S-- statistics  --L-- leaderboard  --O-- settings", 0., -40., 28.);
+    commands.spawn((wardrobe_text, MenuItem));
+
+    let tutorial_text = text(&asset_server, "New here? Press --T-- for the tutorial", 0., -80., 28.);
+    commands.spawn((tutorial_text, MenuItem));
+
+    let coop_text = text(&asset_server, "Two players? --C-- co-op   --V-- versus", 0., -115., 28.);
+    commands.spawn((coop_text, MenuItem));
+
+    let daily_text = text(&asset_server, "--D-- daily run, the same fruits for everyone today", 0., -150., 28.);
+    commands.spawn((daily_text, MenuItem));
 }
 
 
@@ -56,8 +72,53 @@ fn exit(mut commands: Commands, query: Query<Entity, With<MenuItem>>) {
 }
 
 
-fn enter_game(keys: Res<Input<KeyCode>>, mut app_state: ResMut<NextState<AppState>>) {
+fn enter_game(
+    keys: Res<Input<KeyCode>>,
+    cli: Option<Res<Cli>>,
+    mut app_state: ResMut<NextState<AppState>>,
+    mut mode: ResMut<GameMode>,
+    mut chosen: ResMut<ChosenSeed>
+) {
+    // a daily or retried seed only lasts until the player goes back to the menu
+    let cli_seed = ChosenSeed(cli.as_ref().and_then(|cli| cli.seed));
+
     if keys.just_pressed(KeyCode::Return) {
+        // --mode picks what Enter starts, solo otherwise
+        mode.set_if_neq(cli.and_then(|cli| cli.mode).unwrap_or(GameMode::Solo));
+        chosen.set_if_neq(cli_seed);
+        app_state.set(AppState::InGame);
+    }
+    if keys.just_pressed(KeyCode::C) {
+        mode.set_if_neq(GameMode::Coop);
+        chosen.set_if_neq(cli_seed);
+        app_state.set(AppState::InGame);
+    }
+    if keys.just_pressed(KeyCode::V) {
+        mode.set_if_neq(GameMode::Versus);
+        chosen.set_if_neq(cli_seed);
         app_state.set(AppState::InGame);
     }
+    if keys.just_pressed(KeyCode::D) {
+        mode.set_if_neq(GameMode::Solo);
+        chosen.set_if_neq(ChosenSeed(Some(daily_seed())));
+        app_state.set(AppState::InGame);
+    }
+    if keys.just_pressed(KeyCode::W) {
+        app_state.set(AppState::Wardrobe);
+    }
+    if keys.just_pressed(KeyCode::Q) {
+        app_state.set(AppState::Achievements);
+    }
+    if keys.just_pressed(KeyCode::S) {
+        app_state.set(AppState::Stats);
+    }
+    if keys.just_pressed(KeyCode::T) {
+        app_state.set(AppState::Tutorial);
+    }
+    if keys.just_pressed(KeyCode::L) {
+        app_state.set(AppState::Leaderboard);
+    }
+    if keys.just_pressed(KeyCode::O) {
+        app_state.set(AppState::Settings);
+    }
 }
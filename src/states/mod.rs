@@ -2,3 +2,12 @@ pub mod finish;
 pub mod session;
 pub mod pause;
 pub mod menu;
+pub mod wardrobe;
+pub mod achievements;
+pub mod stats;
+pub mod loading;
+pub mod settings;
+pub mod tutorial;
+pub mod demo;
+pub mod leaderboard;
+
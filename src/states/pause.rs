@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::global::AppState;
+use crate::global::{AppState, SCREEN_SCALE};
 use crate::utils::ui::text;
 
 
@@ -25,7 +25,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     // add background image
     commands.spawn((SpriteBundle {
         texture: asset_server.load("images/menu.png"),
-        transform: Transform::from_xyz(0., 0., 10.).with_scale(Vec3::splat(7.5)),
+        transform: Transform::from_xyz(0., 0., 10.).with_scale(Vec3::splat(SCREEN_SCALE)),
         ..default()
     }, PauseItem));
 
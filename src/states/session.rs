@@ -5,7 +5,9 @@ use crate::chef::ChefPlugin;
 use crate::level::LevelPlugin;
 use crate::info::InfoPlugin;
 
-use crate::global::AppState;
+use crate::global::{order_gameplay, AppState, GameplaySet};
+#[cfg(feature = "dev")]
+use crate::console::{arg, ConsoleExt};
 
 
 pub struct SessionPlugin;
@@ -16,9 +18,64 @@ impl Plugin for SessionPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_plugins((FruitPlugin, ChefPlugin, LevelPlugin, InfoPlugin))
+            .insert_resource(GameMode::Solo)
             .add_systems(Startup, setup)
-            .add_systems(Update, (pause, check_lives).run_if(in_state(AppState::InGame)))
+            .add_systems(Update, (
+                pause,
+                // after the whole frame, so a replay ends on the same frame as the run
+                check_lives.after(GameplaySet::Garbage)
+            ).run_if(in_state(AppState::InGame)))
         ;
+
+        order_gameplay(app);
+
+        #[cfg(feature = "dev")]
+        app
+            .init_resource::<GodMode>()
+            .add_systems(Update, keep_lives
+                .after(GameplaySet::Garbage)
+                .before(check_lives)
+                .run_if(resource_equals(GodMode(true)))
+            )
+            .add_console_command("lives", "lives <n>", lives_command)
+            .add_console_command("boosts", "boosts <n>", boosts_command)
+            .add_console_command("godmode", "godmode", godmode_command);
+    }
+}
+
+
+pub const MAX_PLAYERS: usize = 2;
+pub const MAX_LIVES: u32 = 5;
+
+
+/// Picked on the main menu, kept across restarts.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameMode {
+    Solo,
+    /// Two chefs share lives and boosts.
+    Coop,
+    /// Two chefs on their own half of the playfield, each with their own lives.
+    Versus
+}
+
+impl GameMode {
+    pub const fn players(self) -> usize {
+        match self {
+            Self::Solo => 1,
+            Self::Coop | Self::Versus => 2
+        }
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Solo => "solo",
+            Self::Coop => "coop",
+            Self::Versus => "versus"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Solo, Self::Coop, Self::Versus].into_iter().find(|mode| mode.name() == name)
     }
 }
 
@@ -27,20 +84,60 @@ impl Plugin for SessionPlugin {
 pub struct Session {
     pub level: u32,
     pub lives_left: u32,
+    /// Score of the whole team.
     pub score: u32,
-    pub boosts: u32
+    pub boosts: u32,
+    pub player_scores: [u32; MAX_PLAYERS],
+    /// Lives of each side in versus, `lives_left` is used by every other mode.
+    pub player_lives: [u32; MAX_PLAYERS]
 }
 
 impl Session {
     pub const fn default() -> Self {
-        Self { level: 1, lives_left: 5, score: 0, boosts: 0 }
+        Self {
+            level: 1,
+            lives_left: MAX_LIVES,
+            score: 0,
+            boosts: 0,
+            player_scores: [0; MAX_PLAYERS],
+            player_lives: [MAX_LIVES; MAX_PLAYERS]
+        }
+    }
+
+    /// Lives of a side, or the shared lives for fruits that belong to nobody.
+    pub fn lives_mut(&mut self, side: Option<usize>) -> &mut u32 {
+        match side.and_then(|side| self.player_lives.get_mut(side)) {
+            Some(lives) => lives,
+            None => &mut self.lives_left
+        }
     }
-    pub fn text(&self) -> String {
-        format!(
-            "boosts: {}
-            score: {}", 
-            self.boosts, self.score
-        )
+
+    pub fn is_over(&self) -> bool {
+        self.lives_left == 0 || self.player_lives.contains(&0)
+    }
+
+    /// Player who won a versus game, `None` on a draw.
+    pub fn winner(&self) -> Option<usize> {
+        let [first, second] = self.player_lives;
+
+        match (first > 0, second > 0) {
+            (true, false) => Some(0),
+            (false, true) => Some(1),
+            // both sides ran out in the same frame, points decide
+            _ => match self.player_scores[0].cmp(&self.player_scores[1]) {
+                std::cmp::Ordering::Greater => Some(0),
+                std::cmp::Ordering::Less => Some(1),
+                std::cmp::Ordering::Equal => None
+            }
+        }
+    }
+
+    pub fn add_score(&mut self, player: usize, points: u32) {
+        self.score += points;
+
+        if let Some(score) = self.player_scores.get_mut(player) {
+            *score += points;
+        }
     }
 }
 
@@ -51,7 +148,7 @@ pub fn setup(mut commands: Commands) {
 
 
 fn check_lives(session: Res<Session>, mut app_state: ResMut<NextState<AppState>>) {
-    if session.lives_left == 0 {
+    if session.is_over() {
         app_state.set(AppState::Finish);
     }
 }
@@ -62,3 +159,86 @@ fn pause(keys: Res<Input<KeyCode>>, mut app_state: ResMut<NextState<AppState>>)
         app_state.set(AppState::Paused);
     }
 }
+
+
+
+/// Lives stay full while it's on.
+#[cfg(feature = "dev")]
+#[derive(Resource, Default, PartialEq, Eq)]
+struct GodMode(bool);
+
+
+#[cfg(feature = "dev")]
+fn keep_lives(mut session: ResMut<Session>) {
+    session.lives_left = MAX_LIVES;
+    session.player_lives = [MAX_LIVES; MAX_PLAYERS];
+}
+
+
+#[cfg(feature = "dev")]
+fn lives_command(world: &mut World, args: &[&str]) -> Result<String, String> {
+    let lives: u32 = arg(args, 0)?;
+    if lives > MAX_LIVES {
+        return Err(format!("at most {MAX_LIVES} lives"));
+    }
+
+    let mut session = world.resource_mut::<Session>();
+
+    session.lives_left = lives;
+    session.player_lives = [lives; MAX_PLAYERS];
+    Ok(format!("{lives} lives"))
+}
+
+
+#[cfg(feature = "dev")]
+fn boosts_command(world: &mut World, args: &[&str]) -> Result<String, String> {
+    let boosts: u32 = arg(args, 0)?;
+    world.resource_mut::<Session>().boosts = boosts;
+    Ok(format!("{boosts} boosts"))
+}
+
+
+#[cfg(feature = "dev")]
+#[allow(clippy::unnecessary_wraps)]
+fn godmode_command(world: &mut World, _: &[&str]) -> Result<String, String> {
+    let mut god_mode = world.resource_mut::<GodMode>();
+    god_mode.0 = !god_mode.0;
+    Ok(format!("godmode {}", if god_mode.0 { "on" } else { "off" }))
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Session, MAX_LIVES};
+
+    #[test]
+    fn test_it_splits_score_per_player() {
+        let mut session = Session::default();
+
+        session.add_score(0, 2);
+        session.add_score(1, 3);
+        // unknown players still count for the team
+        session.add_score(7, 1);
+
+        assert_eq!(session.score, 6);
+        assert_eq!(session.player_scores, [2, 3]);
+    }
+
+    #[test]
+    fn test_it_declares_a_winner() {
+        let mut session = Session::default();
+        assert!(!session.is_over());
+
+        *session.lives_mut(Some(1)) = 0;
+        assert!(session.is_over());
+        assert_eq!(session.winner(), Some(0));
+        assert_eq!(session.lives_left, MAX_LIVES);
+
+        // both out at once, points decide and a tie is a draw
+        *session.lives_mut(Some(0)) = 0;
+        assert_eq!(session.winner(), None);
+        session.add_score(1, 4);
+        assert_eq!(session.winner(), Some(1));
+    }
+}
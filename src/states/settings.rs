@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+
+use crate::global::{AppState, SCREEN_SCALE};
+use crate::settings::{Settings, Toggle};
+use crate::utils::save::SaveData;
+use crate::utils::ui::text;
+
+
+pub struct SettingsScreenPlugin;
+
+impl Plugin for SettingsScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(Selected(0))
+            .add_systems(OnEnter::<AppState>(AppState::Settings), setup)
+            .add_systems(OnExit::<AppState>(AppState::Settings), exit)
+            .add_systems(Update, (browse, update_text).chain().run_if(in_state(AppState::Settings)))
+        ;
+    }
+}
+
+
+#[derive(Component)]
+struct SettingsItem;
+
+#[derive(Component)]
+struct ToggleText(usize);
+
+/// Index of the highlighted toggle.
+#[derive(Resource)]
+struct Selected(usize);
+
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((SpriteBundle {
+        texture: asset_server.load("images/menu.png"),
+        transform: Transform::from_xyz(0., 0., 10.).with_scale(Vec3::splat(SCREEN_SCALE)),
+        ..default()
+    }, SettingsItem));
+
+    let title = text(&asset_server, "Settings", 0., 200., 60.);
+    commands.spawn((title, SettingsItem));
+
+    for i in 0..Toggle::ALL.len() {
+        let line = text(&asset_server, "", 0., 120. - 45. * i as f32, 32.);
+        commands.spawn((line, ToggleText(i), SettingsItem));
+    }
+
+    let hint = text(&asset_server, "Up/Down - select, --Enter-- to switch, --Esc-- to go back", 0., -200., 28.);
+    commands.spawn((hint, SettingsItem));
+}
+
+
+fn exit(mut commands: Commands, query: Query<Entity, With<SettingsItem>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+
+fn browse(
+    keys: Res<Input<KeyCode>>,
+    mut selected: ResMut<Selected>,
+    mut settings: ResMut<Settings>,
+    mut save: ResMut<SaveData>,
+    mut app_state: ResMut<NextState<AppState>>
+) {
+    let count = Toggle::ALL.len();
+
+    if keys.just_pressed(KeyCode::Down) {
+        selected.0 = (selected.0 + 1) % count;
+    }
+    if keys.just_pressed(KeyCode::Up) {
+        selected.0 = (selected.0 + count - 1) % count;
+    }
+    if keys.just_pressed(KeyCode::Return) {
+        settings.flip(Toggle::ALL[selected.0]);
+        settings.store(&mut save);
+    }
+    if keys.just_pressed(KeyCode::Escape) {
+        app_state.set(AppState::MainMenu);
+    }
+}
+
+
+fn update_text(selected: Res<Selected>, settings: Res<Settings>, mut query: Query<(&mut Text, &ToggleText)>) {
+    for (mut text, line) in &mut query {
+        let toggle = Toggle::ALL[line.0];
+        let value = if settings.is_on(toggle) { "on" } else { "off" };
+        let cursor = if selected.0 == line.0 { ">" } else { " " };
+
+        text.sections[0].value = format!("{cursor} {}: {value}", toggle.name());
+    }
+}
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+
+use crate::fruits::fruit::FruitType;
+use crate::global::{AppState, SCREEN_SCALE};
+use crate::stats::{format_time, LifetimeStats};
+use crate::utils::record;
+use crate::utils::save::SaveData;
+use crate::utils::ui::text;
+
+
+pub struct StatsScreenPlugin;
+
+impl Plugin for StatsScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter::<AppState>(AppState::Stats), setup)
+            .add_systems(OnExit::<AppState>(AppState::Stats), exit)
+            .add_systems(Update, back.run_if(in_state(AppState::Stats)))
+        ;
+    }
+}
+
+
+#[derive(Component)]
+struct StatsItem;
+
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, save: Res<SaveData>) {
+    commands.spawn((SpriteBundle {
+        texture: asset_server.load("images/menu.png"),
+        transform: Transform::from_xyz(0., 0., 10.).with_scale(Vec3::splat(SCREEN_SCALE)),
+        ..default()
+    }, StatsItem));
+
+    let title = text(&asset_server, "Statistics", 0., 200., 60.);
+    commands.spawn((title, StatsItem));
+
+    let stats = LifetimeStats::load(&save);
+
+    let sliced = FruitType::ALL
+        .iter()
+        .map(|fruit| format!("{} {}", fruit.name(), stats.sliced.get(fruit).unwrap_or(&0)))
+        .collect::<Vec<_>>()
+        .join("  ");
+
+    let lines = [
+        format!("Runs played: {}", stats.runs),
+        format!("Record: {}   Average score: {}", record::read_file(), stats.average_score()),
+        format!("Fruits sliced: {}   Missed: {}", stats.total_sliced(), stats.missed),
+        sliced,
+        format!("Boosts used: {}   Longest combo: {}", stats.boosts_used, stats.longest_combo),
+        format!("Time played: {}", format_time(stats.seconds_played)),
+    ];
+
+    for (i, line) in lines.iter().enumerate() {
+        let line = text(&asset_server, line, 0., 120. - 45. * i as f32, 30.);
+        commands.spawn((line, StatsItem));
+    }
+
+    let hint = text(&asset_server, "Press --Esc-- to go back", 0., -200., 28.);
+    commands.spawn((hint, StatsItem));
+}
+
+
+fn exit(mut commands: Commands, query: Query<Entity, With<StatsItem>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+
+fn back(keys: Res<Input<KeyCode>>, mut app_state: ResMut<NextState<AppState>>) {
+    if keys.just_pressed(KeyCode::Escape) {
+        app_state.set(AppState::MainMenu);
+    }
+}
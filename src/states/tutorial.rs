@@ -0,0 +1,269 @@
+use bevy::prelude::*;
+
+use crate::chef::Player;
+use crate::fruits::boost::BoostUsedEvent;
+use crate::fruits::fruit::{Fruit, FruitType, SliceEvent};
+use crate::fruits::sprite::{create_pineapple, create_pome, create_sprite, FruitTextures};
+use crate::global::{AppState, SPAWN_Y};
+use crate::sound::{SoundEvent, SoundType};
+use crate::utils::random::randint;
+use crate::utils::ui::text;
+
+use super::session::{GameMode, Session};
+
+/// Tutorial fruits fall slower than in the game.
+const SLOW_FALL_SPEED: f32 = 150.;
+const COMBO_FRUITS: usize = 3;
+/// How far the chef has to walk to each side.
+const MOVE_DISTANCE: f32 = 150.;
+const BAG_SECONDS: f32 = 0.5;
+
+
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter::<AppState>(AppState::Tutorial), setup)
+            .add_systems(OnExit::<AppState>(AppState::Tutorial), exit)
+            .add_systems(
+                Update,
+                (advance, spawn_step_fruits, update_text, back).chain().run_if(in_state(AppState::Tutorial))
+            )
+        ;
+    }
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    Move,
+    Bag,
+    Slice,
+    Combo,
+    Pineapple,
+    Boost,
+    Pome,
+    Done
+}
+
+impl Step {
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Move => Self::Bag,
+            Self::Bag => Self::Slice,
+            Self::Slice => Self::Combo,
+            Self::Combo => Self::Pineapple,
+            Self::Pineapple => Self::Boost,
+            Self::Boost => Self::Pome,
+            Self::Pome | Self::Done => Self::Done
+        }
+    }
+
+    const fn instruction(self) -> &'static str {
+        match self {
+            Self::Move => "Walk with --Left-- and --Right--",
+            Self::Bag => "Hold --S-- to open the trash bag",
+            Self::Slice => "Stand under the fruit and press --F-- to slice it",
+            Self::Combo => "Slice the whole column with one swing",
+            Self::Pineapple => "Slice the pineapple to get a boost",
+            Self::Boost => "Press --A-- to fire the boost",
+            Self::Pome => "Slice the pome to win back a life",
+            Self::Done => "Well done! Press --Enter-- to go back to the menu"
+        }
+    }
+}
+
+
+/// What the player did so far in the current step.
+#[derive(Resource)]
+struct Tutorial {
+    step: Step,
+    walked_left: bool,
+    walked_right: bool,
+    bag_seconds: f32
+}
+
+impl Tutorial {
+    const fn new() -> Self {
+        Self { step: Step::Move, walked_left: false, walked_right: false, bag_seconds: 0. }
+    }
+}
+
+
+#[derive(Component)]
+struct TutorialItem;
+
+#[derive(Component)]
+struct InstructionText;
+
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut session: ResMut<Session>,
+    mut mode: ResMut<GameMode>,
+    fruits: Query<Entity, With<Fruit>>
+) {
+    commands.insert_resource(Tutorial::new());
+    *session = Session::default();
+    mode.set_if_neq(GameMode::Solo);
+
+    for entity in &fruits {
+        commands.entity(entity).despawn();
+    }
+
+    let mut instruction = text(&asset_server, "", 0., 230., 36.);
+    instruction.transform.translation.z = 20.;
+    commands.spawn((instruction, InstructionText, TutorialItem));
+
+    let mut hint = text(&asset_server, "Tutorial   --Esc-- to leave", 0., 280., 24.);
+    hint.transform.translation.z = 20.;
+    commands.spawn((hint, TutorialItem));
+}
+
+
+fn exit(
+    mut commands: Commands,
+    mut session: ResMut<Session>,
+    items: Query<Entity, With<TutorialItem>>,
+    fruits: Query<Entity, With<Fruit>>
+) {
+    for entity in items.iter().chain(&fruits) {
+        commands.entity(entity).despawn();
+    }
+
+    *session = Session::default();
+}
+
+
+fn advance(
+    time: Res<Time>,
+    keys: Res<Input<KeyCode>>,
+    mut tutorial: ResMut<Tutorial>,
+    chefs: Query<&Transform, With<Player>>,
+    mut slices: EventReader<SliceEvent>,
+    mut boosts: EventReader<BoostUsedEvent>,
+    mut sound: EventWriter<SoundEvent>
+) {
+    for transform in &chefs {
+        tutorial.walked_left |= transform.translation.x < -MOVE_DISTANCE;
+        tutorial.walked_right |= transform.translation.x > MOVE_DISTANCE;
+    }
+
+    if keys.pressed(KeyCode::S) {
+        tutorial.bag_seconds += time.delta_seconds();
+    }
+
+    let swings: Vec<&SliceEvent> = slices.iter().filter(|slice| !slice.boost).collect();
+    let caught = |fruit: FruitType| swings.iter().any(|slice| slice.fruits.contains(&fruit));
+
+    let done = match tutorial.step {
+        Step::Move => tutorial.walked_left && tutorial.walked_right,
+        Step::Bag => tutorial.bag_seconds >= BAG_SECONDS,
+        Step::Slice => !swings.is_empty(),
+        Step::Combo => swings.iter().any(|slice| slice.fruits.len() >= COMBO_FRUITS),
+        Step::Pineapple => caught(FruitType::PINEAPPLE),
+        Step::Boost => boosts.iter().count() > 0,
+        Step::Pome => caught(FruitType::POME),
+        Step::Done => false
+    };
+    boosts.clear();
+
+    if done {
+        tutorial.step = tutorial.step.next();
+        sound.send(SoundEvent::sound(SoundType::ACHIEVEMENT));
+    }
+}
+
+
+fn slow_fruit(fruit_type: FruitType) -> Fruit {
+    let mut fruit = Fruit::new();
+    fruit.fruit_type = fruit_type;
+    fruit.fall_speed = SLOW_FALL_SPEED;
+    fruit
+}
+
+
+/// Keeps the fruits the current step needs on screen, missed ones come back.
+fn spawn_step_fruits(
+    mut commands: Commands,
+    tutorial: Res<Tutorial>,
+    fruit_assets: Res<FruitTextures>,
+    mut session: ResMut<Session>,
+    fruits: Query<&Fruit>
+) {
+    if fruits.iter().any(|fruit| !fruit.sliced) {
+        return;
+    }
+
+    let x = randint(-300, 300) as f32;
+
+    let column = match tutorial.step {
+        Step::Slice => 1,
+        Step::Combo | Step::Boost => COMBO_FRUITS,
+        _ => 0
+    };
+
+    for i in 0..column {
+        let (sprite, fruit_type) = create_sprite(&fruit_assets, x, i as f32 * 30. + SPAWN_Y - 20., 5. + i as f32);
+        commands.spawn((sprite, slow_fruit(fruit_type)));
+    }
+
+    match tutorial.step {
+        Step::Pineapple => {
+            let mut fruit = slow_fruit(FruitType::PINEAPPLE);
+            fruit.rotation_speed = 0.;
+            commands.spawn((create_pineapple(&fruit_assets, x, SPAWN_Y), fruit));
+        }
+        Step::Pome => {
+            // one life short so the pome has something to give back
+            session.lives_left = session.lives_left.min(4);
+            commands.spawn((create_pome(&fruit_assets, x, SPAWN_Y), slow_fruit(FruitType::POME)));
+        }
+        _ => {}
+    }
+}
+
+
+fn update_text(tutorial: Res<Tutorial>, mut query: Query<&mut Text, With<InstructionText>>) {
+    if !tutorial.is_changed() {
+        return;
+    }
+
+    for mut text in &mut query {
+        let instruction = tutorial.step.instruction();
+
+        if text.sections[0].value != instruction {
+            text.sections[0].value = instruction.to_string();
+        }
+    }
+}
+
+
+fn back(keys: Res<Input<KeyCode>>, tutorial: Res<Tutorial>, mut app_state: ResMut<NextState<AppState>>) {
+    if keys.just_pressed(KeyCode::Escape) || (tutorial.step == Step::Done && keys.just_pressed(KeyCode::Return)) {
+        app_state.set(AppState::MainMenu);
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::Step;
+
+    #[test]
+    fn test_it_walks_through_every_step() {
+        let mut step = Step::Move;
+        let mut count = 0;
+
+        while step != Step::Done {
+            step = step.next();
+            count += 1;
+        }
+
+        assert_eq!(count, 7);
+        assert_eq!(Step::Done.next(), Step::Done);
+    }
+}
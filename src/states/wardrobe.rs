@@ -0,0 +1,204 @@
+use bevy::prelude::*;
+
+use crate::achievements::ACHIEVEMENTS;
+use crate::animation::SpriteAnimation;
+use crate::chef::skins::{is_unlocked, SkinAtlases, Unlock, Wardrobe, SKINS, TRAILS};
+use crate::chef::{sprite, trail};
+use crate::global::{AppState, SCREEN_SCALE};
+use crate::utils::save::SaveData;
+use crate::utils::ui::text;
+
+
+const TRY_KEY: KeyCode = KeyCode::F;
+/// Where the chef wearing the browsed skin stands, below the hints.
+const PREVIEW_POSITION: Vec3 = Vec3::new(0., -170., 11.);
+
+
+pub struct WardrobePlugin;
+
+impl Plugin for WardrobePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter::<AppState>(AppState::Wardrobe), setup)
+            .add_systems(OnExit::<AppState>(AppState::Wardrobe), exit)
+            .add_systems(Update, (browse, confirm, update_text, update_preview, try_on).chain().run_if(in_state(AppState::Wardrobe)))
+        ;
+    }
+}
+
+
+#[derive(Component)]
+struct WardrobeItem;
+
+#[derive(Component)]
+struct SkinText;
+
+#[derive(Component)]
+struct TrailText;
+
+#[derive(Component)]
+struct Preview;
+
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    atlases: Res<SkinAtlases>,
+    wardrobe: Res<Wardrobe>
+) {
+    commands.spawn((SpriteBundle {
+        texture: asset_server.load("images/menu.png"),
+        transform: Transform::from_xyz(0., 0., 10.).with_scale(Vec3::splat(SCREEN_SCALE)),
+        ..default()
+    }, WardrobeItem));
+
+    let title = text(&asset_server, "Wardrobe", 0., 180., 70.);
+    commands.spawn((title, WardrobeItem));
+
+    let skin = text(&asset_server, "", 0., 90., 40.);
+    commands.spawn((skin, SkinText, WardrobeItem));
+
+    let trail = text(&asset_server, "", 0., 40., 40.);
+    commands.spawn((trail, TrailText, WardrobeItem));
+
+    let hint = text(&asset_server, "Left/Right - skin, Up/Down - blade, F - try it", 0., -10., 28.);
+    commands.spawn((hint, WardrobeItem));
+
+    let hint = text(&asset_server, "--Enter-- to equip, --Esc-- to go back", 0., -45., 28.);
+    commands.spawn((hint, WardrobeItem));
+
+    let skin = wardrobe.skin();
+    let mut preview = sprite::get_sprite(atlases.0[wardrobe.skin].clone(), skin);
+    preview.transform.translation = PREVIEW_POSITION;
+    commands.spawn((preview, SpriteAnimation::new(sprite::clips(skin.frames)), Preview, WardrobeItem));
+}
+
+
+fn exit(
+    mut commands: Commands,
+    query: Query<Entity, With<WardrobeItem>>,
+    save: Res<SaveData>,
+    mut wardrobe: ResMut<Wardrobe>
+) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+
+    // drop the preview, keep whatever is equipped
+    let equipped = Wardrobe::load(&save);
+    if *wardrobe != equipped {
+        *wardrobe = equipped;
+    }
+}
+
+
+fn browse(keys: Res<Input<KeyCode>>, mut wardrobe: ResMut<Wardrobe>) {
+    if keys.just_pressed(KeyCode::Right) {
+        wardrobe.skin = (wardrobe.skin + 1) % SKINS.len();
+    }
+    if keys.just_pressed(KeyCode::Left) {
+        wardrobe.skin = (wardrobe.skin + SKINS.len() - 1) % SKINS.len();
+    }
+    if keys.just_pressed(KeyCode::Up) {
+        wardrobe.trail = (wardrobe.trail + 1) % TRAILS.len();
+    }
+    if keys.just_pressed(KeyCode::Down) {
+        wardrobe.trail = (wardrobe.trail + TRAILS.len() - 1) % TRAILS.len();
+    }
+}
+
+
+fn confirm(
+    keys: Res<Input<KeyCode>>,
+    wardrobe: Res<Wardrobe>,
+    mut save: ResMut<SaveData>,
+    mut app_state: ResMut<NextState<AppState>>
+) {
+    if keys.just_pressed(KeyCode::Escape) {
+        app_state.set(AppState::MainMenu);
+    }
+
+    if keys.just_pressed(KeyCode::Return) {
+        let skin = wardrobe.skin();
+        let trail = wardrobe.trail();
+
+        if is_unlocked(&save, skin.id, skin.unlock) && is_unlocked(&save, trail.id, trail.unlock) {
+            wardrobe.store(&mut save);
+            app_state.set(AppState::MainMenu);
+        }
+    }
+}
+
+
+fn describe(save: &SaveData, kind: &str, id: &str, name: &str, unlock: Unlock) -> String {
+    if is_unlocked(save, id, unlock) {
+        return format!("< {kind}: {name} >");
+    }
+
+    let requirement = match unlock {
+        Unlock::Free => String::new(),
+        Unlock::Score(score) => format!("score {score}"),
+        Unlock::Achievement(id) => ACHIEVEMENTS
+            .iter()
+            .find(|achievement| achievement.id == id)
+            .map_or_else(String::new, |achievement| achievement.description.to_lowercase())
+    };
+
+    format!("< {kind}: {name} (locked, {requirement}) >")
+}
+
+
+fn update_text(
+    wardrobe: Res<Wardrobe>,
+    save: Res<SaveData>,
+    mut skin_text: Query<&mut Text, (With<SkinText>, Without<TrailText>)>,
+    mut trail_text: Query<&mut Text, (With<TrailText>, Without<SkinText>)>
+) {
+    let skin = wardrobe.skin();
+    let trail = wardrobe.trail();
+
+    for mut text in &mut skin_text {
+        text.sections[0].value = describe(&save, "Skin", skin.id, skin.name, skin.unlock);
+    }
+
+    for mut text in &mut trail_text {
+        text.sections[0].value = describe(&save, "Blade", trail.id, trail.name, trail.unlock);
+    }
+}
+
+
+fn update_preview(
+    wardrobe: Res<Wardrobe>,
+    atlases: Res<SkinAtlases>,
+    mut query: Query<(&mut Handle<TextureAtlas>, &mut TextureAtlasSprite, &mut SpriteAnimation), With<Preview>>
+) {
+    if !wardrobe.is_changed() {
+        return;
+    }
+
+    let skin = wardrobe.skin();
+
+    for (mut texture_atlas, mut sprite, mut animation) in &mut query {
+        *texture_atlas = atlases.0[wardrobe.skin].clone();
+        *animation = SpriteAnimation::new(sprite::clips(skin.frames));
+        sprite.index = animation.index();
+    }
+}
+
+
+/// Swings the browsed blade with the browsed skin, locked ones too.
+fn try_on(
+    mut commands: Commands,
+    keys: Res<Input<KeyCode>>,
+    wardrobe: Res<Wardrobe>,
+    mut query: Query<(&Transform, &mut SpriteAnimation), With<Preview>>
+) {
+    if !keys.just_pressed(TRY_KEY) {
+        return;
+    }
+
+    for (transform, mut animation) in &mut query {
+        animation.play("slice");
+        trail::spawn_blade(&mut commands, wardrobe.trail(), transform.translation);
+    }
+}
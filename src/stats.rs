@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::fruits::boost::BoostUsedEvent;
+use crate::fruits::fruit::{FruitType, SliceEvent};
+use crate::fruits::penalty::WaveEvent;
+use crate::global::AppState;
+use crate::states::session::Session;
+use crate::utils::save::SaveData;
+
+
+const FROM_MENU_ENTER: OnTransition<AppState> = OnTransition::<AppState> {
+    from: AppState::MainMenu,
+    to: AppState::InGame
+};
+
+const FROM_FINISH_ENTER: OnTransition<AppState> = OnTransition::<AppState> {
+    from: AppState::Finish,
+    to: AppState::InGame
+};
+
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(RunStats::default())
+            .add_systems(FROM_MENU_ENTER, reset_run)
+            .add_systems(FROM_FINISH_ENTER, reset_run)
+            .add_systems(Update, track.run_if(in_state(AppState::InGame)))
+            .add_systems(OnEnter::<AppState>(AppState::Finish), save_run)
+        ;
+    }
+}
+
+
+/// Numbers collected during a single run.
+#[derive(Resource, Default, Clone, Debug, PartialEq)]
+pub struct RunStats {
+    pub sliced: HashMap<FruitType, u32>,
+    pub missed: u32,
+    pub boosts_used: u32,
+    pub longest_combo: u32,
+    pub seconds: f32
+}
+
+impl RunStats {
+    pub fn total_sliced(&self) -> u32 {
+        self.sliced.values().sum()
+    }
+
+    pub fn record_slice(&mut self, event: &SliceEvent) {
+        for fruit in &event.fruits {
+            *self.sliced.entry(fruit.clone()).or_default() += 1;
+        }
+
+        if !event.boost {
+            self.longest_combo = self.longest_combo.max(event.fruits.len() as u32);
+        }
+    }
+}
+
+
+/// Totals over every finished run, persisted in `SaveData` under `stats.*` keys.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct LifetimeStats {
+    pub runs: u32,
+    pub sliced: HashMap<FruitType, u32>,
+    pub missed: u32,
+    pub boosts_used: u32,
+    pub longest_combo: u32,
+    pub total_score: u32,
+    pub seconds_played: u32
+}
+
+impl LifetimeStats {
+    pub fn load(save: &SaveData) -> Self {
+        let sliced = FruitType::ALL
+            .iter()
+            .map(|fruit| (fruit.clone(), save.get_u32(&format!("stats.sliced.{}", fruit.name()))))
+            .collect();
+
+        Self {
+            runs: save.get_u32("stats.runs"),
+            sliced,
+            missed: save.get_u32("stats.missed"),
+            boosts_used: save.get_u32("stats.boosts"),
+            longest_combo: save.get_u32("stats.combo"),
+            total_score: save.get_u32("stats.score"),
+            seconds_played: save.get_u32("stats.seconds")
+        }
+    }
+
+    pub fn store(&self, save: &mut SaveData) {
+        for (fruit, count) in &self.sliced {
+            save.set(&format!("stats.sliced.{}", fruit.name()), count);
+        }
+
+        save.set("stats.runs", self.runs);
+        save.set("stats.missed", self.missed);
+        save.set("stats.boosts", self.boosts_used);
+        save.set("stats.combo", self.longest_combo);
+        save.set("stats.score", self.total_score);
+        save.set("stats.seconds", self.seconds_played);
+    }
+
+    pub fn add_run(&mut self, run: &RunStats, score: u32) {
+        for (fruit, count) in &run.sliced {
+            *self.sliced.entry(fruit.clone()).or_default() += count;
+        }
+
+        self.runs += 1;
+        self.missed += run.missed;
+        self.boosts_used += run.boosts_used;
+        self.longest_combo = self.longest_combo.max(run.longest_combo);
+        self.total_score += score;
+        self.seconds_played += run.seconds.round() as u32;
+    }
+
+    pub fn total_sliced(&self) -> u32 {
+        self.sliced.values().sum()
+    }
+
+    pub const fn average_score(&self) -> u32 {
+        if self.runs == 0 {
+            return 0;
+        }
+        self.total_score / self.runs
+    }
+}
+
+
+/// Formats seconds as `h:mm:ss` or `m:ss`.
+pub fn format_time(seconds: u32) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+
+fn reset_run(mut run: ResMut<RunStats>) {
+    *run = RunStats::default();
+}
+
+
+fn track(
+    time: Res<Time>,
+    mut run: ResMut<RunStats>,
+    mut slices: EventReader<SliceEvent>,
+    mut waves: EventReader<WaveEvent>,
+    mut boosts: EventReader<BoostUsedEvent>
+) {
+    run.seconds += time.delta_seconds();
+
+    for event in &mut slices {
+        run.record_slice(event);
+    }
+
+    run.missed += waves.len() as u32;
+    run.boosts_used += boosts.len() as u32;
+    waves.clear();
+    boosts.clear();
+}
+
+
+fn save_run(run: Res<RunStats>, session: Res<Session>, mut save: ResMut<SaveData>) {
+    let mut lifetime = LifetimeStats::load(&save);
+    lifetime.add_run(&run, session.score);
+    lifetime.store(&mut save);
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::fruits::fruit::{FruitType, SliceEvent};
+    use crate::utils::save::SaveData;
+    use super::{format_time, LifetimeStats, RunStats};
+
+    #[test]
+    fn test_it_accumulates_runs() {
+        let mut run = RunStats::default();
+        run.record_slice(&SliceEvent { fruits: vec![FruitType::APPLE, FruitType::APPLE, FruitType::BANANA], boost: false, player: 0 });
+        run.record_slice(&SliceEvent { fruits: vec![FruitType::POME], boost: true, player: 0 });
+        run.missed = 5;
+        run.seconds = 61.4;
+
+        let mut save = SaveData::default();
+        let mut lifetime = LifetimeStats::load(&save);
+        lifetime.add_run(&run, 40);
+        lifetime.add_run(&run, 20);
+        lifetime.store(&mut save);
+
+        let lifetime = LifetimeStats::load(&save);
+        assert_eq!(lifetime.runs, 2);
+        assert_eq!(lifetime.sliced[&FruitType::APPLE], 4);
+        assert_eq!(lifetime.total_sliced(), 8);
+        assert_eq!(lifetime.longest_combo, 3);
+        assert_eq!(lifetime.missed, 10);
+        assert_eq!(lifetime.average_score(), 30);
+        assert_eq!(lifetime.seconds_played, 122);
+    }
+
+    #[test]
+    fn test_it_formats_time() {
+        assert_eq!(format_time(59), "0:59");
+        assert_eq!(format_time(61), "1:01");
+        assert_eq!(format_time(3725), "1:02:05");
+    }
+}
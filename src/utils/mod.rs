@@ -1,3 +1,4 @@
 pub mod random;
 pub mod ui;
-pub mod record;
\ No newline at end of file
+pub mod record;
+pub mod save;
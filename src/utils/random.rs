@@ -1,5 +1,9 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::Resource;
 use rand::prelude::*;
 use rand::distributions::{Bernoulli, Distribution};
+use rand_chacha::ChaCha8Rng;
 
 
 pub fn randint(min: i32, max: i32) -> i32 {
@@ -12,3 +16,83 @@ pub fn probably(salt: f64) -> bool {
     let d = Bernoulli::new(salt).unwrap();
     d.sample(&mut rand::thread_rng())
 }
+
+
+pub fn uniform(min: f64, max: f64) -> f64 {
+    let mut rng = rand::thread_rng();
+    rng.gen_range(min..max)
+}
+
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+
+/// Seed of the daily run, the same for everyone on the same (UTC) day.
+pub fn daily_seed() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs() / SECONDS_PER_DAY)
+}
+
+
+/// Seeded generator for everything that decides how a run plays out,
+/// cosmetic randomness keeps using the functions above.
+/// `ChaCha8Rng` produces the same numbers on every platform and rand release, unlike `StdRng`,
+/// so saved seeds and replays keep playing out the same.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    rng: ChaCha8Rng
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, rng: ChaCha8Rng::seed_from_u64(seed) }
+    }
+
+    /// Fresh seed for a new run.
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_nanos());
+        Self::new(nanos as u64)
+    }
+
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn randint(&mut self, min: i32, max: i32) -> i32 {
+        self.rng.gen_range(min..max)
+    }
+
+    pub fn probably(&mut self, salt: f64) -> bool {
+        self.rng.gen_bool(salt)
+    }
+
+    pub fn uniform(&mut self, min: f32, max: f32) -> f32 {
+        self.rng.gen_range(min..max)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::GameRng;
+
+    #[test]
+    fn test_it_repeats_runs_with_the_same_seed() {
+        let roll = |rng: &mut GameRng| (0..20).map(|_| (rng.randint(0, 100), rng.probably(0.5))).collect::<Vec<_>>();
+
+        let mut first = GameRng::new(42);
+        let mut second = GameRng::new(42);
+
+        assert_eq!(roll(&mut first), roll(&mut second));
+        assert_eq!(first.seed(), 42);
+    }
+
+    #[test]
+    fn test_it_keeps_the_numbers_of_old_seeds() {
+        let mut rng = GameRng::new(7);
+        let rolls = (0..8).map(|_| rng.randint(0, 100)).collect::<Vec<_>>();
+
+        assert_eq!(rolls, [14, 15, 18, 27, 70, 3, 72, 7]);
+    }
+}
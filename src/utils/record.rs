@@ -1,43 +1,72 @@
 use std::fs;
-use std::io::Write;
 use std::path::Path;
+use std::sync::{Mutex, PoisonError};
 
+use bevy::log::warn;
 
-const FILE_NAME: &str = "records.txt";
+use crate::replay::{verify, Replay};
+
+
+pub const FILE_NAME: &str = "records.txt";
+
+/// Last record that played out, so the file is only simulated again once it changes.
+static VERIFIED: Mutex<Option<(String, u32)>> = Mutex::new(None);
 
 
 pub fn is_record(score: u32) -> bool {
     if score > read_file() {
-        return true 
+        return true
     }
     false
 }
 
 
 pub fn read_file() -> u32 {
-    let file_exists = Path::new(FILE_NAME).is_file();
-    if !file_exists {
-        return 0;
-    }
-    
-    let record = fs::read_to_string(FILE_NAME).unwrap();
-    record.parse::<u32>().unwrap_or(0)
+    read_record(Path::new(FILE_NAME))
 }
 
 
-pub fn write_record(record: u32)  {
-    let mut file: fs::File;
-    
-    
-    let file_exists = Path::new(FILE_NAME).is_file();
-    if file_exists {
-        file = fs::OpenOptions::new().write(true).open(FILE_NAME).unwrap();
-    } else {
+/// Score of the record at `path`, 0 when it's missing or its run doesn't play out to that score.
+fn read_record(path: &Path) -> u32 {
+    let Ok(record) = fs::read_to_string(path) else {
+        return 0;
+    };
+
+    // records from before replays hold only the score, they stay the local best
+    // without being checked until a better run replaces them
+    if let Ok(score) = record.trim().parse() {
+        return score;
+    }
+
+    let known = VERIFIED
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+        .filter(|(known, _)| *known == record)
+        .map(|(_, score)| *score);
+    if let Some(score) = known {
+        return score;
+    }
 
-        file = fs::OpenOptions::new().write(true).create(true).open(FILE_NAME).unwrap();
+    match verify(&record) {
+        Ok(score) => {
+            *VERIFIED.lock().unwrap_or_else(PoisonError::into_inner) = Some((record, score));
+            score
+        }
+        Err(error) => {
+            warn!("ignoring {}: {error}", path.display());
+            0
+        }
     }
+}
+
 
-    file.write_all(record.to_string().as_str().as_bytes()).unwrap();
+pub fn write_record(record: u32, replay: &Replay) {
+    let content = replay.to_record(record);
+    fs::write(FILE_NAME, &content).unwrap();
+
+    // the run was just played, no need to play it again
+    *VERIFIED.lock().unwrap_or_else(PoisonError::into_inner) = Some((content, record));
 }
 
 
@@ -45,29 +74,63 @@ pub fn write_record(record: u32)  {
 #[cfg(test)]
 mod tests {
     use std::{path::Path, fs};
+    use crate::replay::{bot_run, Replay};
+    use crate::states::session::GameMode;
     use crate::utils::record::is_record;
-    use super::{write_record, read_file, FILE_NAME};
+    use super::{read_record, write_record, read_file, FILE_NAME};
+
+    /// A short bot run, only runs that play out the same again count as records.
+    fn played_run() -> (u32, Replay) {
+        bot_run(GameMode::Solo, 3, 1, 600)
+    }
 
     #[test]
     fn test_it_writes_record() {
-        write_record(100);
+        let (score, replay) = played_run();
+        write_record(score, &replay);
 
         let result = read_file();
 
-        assert_eq!(result, 100);
+        assert_eq!(result, score);
         cleanup();
     }
 
     #[test]
     fn test_it_checks_record() {
-        write_record(100);
+        let (score, replay) = played_run();
+        write_record(score, &replay);
 
-        assert!(!is_record(50));
-        assert!(is_record(150));
+        assert!(!is_record(score - 1));
+        assert!(is_record(score + 1));
 
         cleanup();
     }
 
+    #[test]
+    fn test_it_ignores_edited_records() {
+        let path = Path::new("edited-records.txt");
+        let (score, replay) = played_run();
+
+        fs::write(path, replay.to_record(score)).unwrap();
+        assert_eq!(read_record(path), score);
+
+        // the checksum is easy to fix up, the replay isn't
+        fs::write(path, replay.to_record(score + 100)).unwrap();
+        assert_eq!(read_record(path), 0);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_it_keeps_records_without_a_run() {
+        let path = Path::new("old-records.txt");
+
+        fs::write(path, "250").unwrap();
+        assert_eq!(read_record(path), 250);
+
+        fs::remove_file(path).unwrap();
+    }
+
     fn cleanup() {
         if Path::new(FILE_NAME).is_file() {
             fs::remove_file(FILE_NAME).unwrap();
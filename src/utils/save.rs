@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+
+use crate::global::AppState;
+
+
+const FILE_NAME: &str = "save.txt";
+
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(SaveData::load())
+            .add_systems(Last, (
+                // gameplay updates progress every few frames, write it between runs
+                persist.run_if(not(in_state(AppState::InGame))),
+                persist_on_exit
+            ))
+        ;
+    }
+}
+
+
+/// Persistent key/value storage shared by everything that needs to
+/// survive a restart (cosmetics, achievements, stats, settings).
+/// Stored as `key=value` lines, lists are comma separated.
+#[derive(Resource, Default, Clone, Debug, PartialEq, Eq)]
+pub struct SaveData {
+    entries: BTreeMap<String, String>
+}
+
+
+impl SaveData {
+    pub fn load() -> Self {
+        if !Path::new(FILE_NAME).is_file() {
+            return Self::default();
+        }
+
+        let content = fs::read_to_string(FILE_NAME).unwrap_or_default();
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Self {
+        let entries = content
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect();
+
+        Self { entries }
+    }
+
+    pub fn serialize(&self) -> String {
+        self.entries
+            .iter()
+            .map(|(key, value)| [key.as_str(), "=", value.as_str(), "\n"].concat())
+            .collect()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    pub fn set(&mut self, key: &str, value: impl ToString) {
+        self.entries.insert(key.to_string(), value.to_string());
+    }
+
+    pub fn get_u32(&self, key: &str) -> u32 {
+        self.get(key).and_then(|value| value.parse().ok()).unwrap_or(0)
+    }
+
+    pub fn get_list(&self, key: &str) -> Vec<&str> {
+        self.get(key)
+            .map(|value| value.split(',').filter(|item| !item.is_empty()).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn contains(&self, key: &str, item: &str) -> bool {
+        self.get_list(key).contains(&item)
+    }
+
+    /// Keeps only the entries whose key passes `keep`.
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.entries.retain(|key, _| keep(key));
+    }
+
+    /// Adds `item` to the list stored under `key`.
+    /// Returns `false` when the item was already there.
+    pub fn push(&mut self, key: &str, item: &str) -> bool {
+        if self.contains(key, item) {
+            return false;
+        }
+
+        let mut list = self.get_list(key);
+        list.push(item);
+        let value = list.join(",");
+        self.set(key, value);
+        true
+    }
+}
+
+
+fn persist(save: Res<SaveData>) {
+    if save.is_changed() && !save.is_added() {
+        write(&save);
+    }
+}
+
+
+fn persist_on_exit(save: Res<SaveData>, events: EventReader<AppExit>) {
+    if !events.is_empty() {
+        write(&save);
+    }
+}
+
+
+fn write(save: &SaveData) {
+    if let Err(error) = fs::write(FILE_NAME, save.serialize()) {
+        warn!("failed to write save data: {error}");
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::SaveData;
+
+    #[test]
+    fn test_it_roundtrips_values() {
+        let mut save = SaveData::default();
+        save.set("skin", "classic");
+        save.set("trail", "flame");
+        save.set("progress.boosts", 3);
+
+        let loaded = SaveData::parse(&save.serialize());
+
+        assert_eq!(loaded, save);
+        assert_eq!(loaded.get("skin"), Some("classic"));
+        assert_eq!(loaded.get("trail"), Some("flame"));
+        assert_eq!(loaded.get("missing"), None);
+        assert_eq!(loaded.get_u32("progress.boosts"), 3);
+        assert_eq!(loaded.get_u32("missing"), 0);
+    }
+
+    #[test]
+    fn test_it_pushes_unique_items() {
+        let mut save = SaveData::default();
+
+        assert!(save.push("skins", "classic"));
+        assert!(save.push("skins", "night"));
+        assert!(!save.push("skins", "classic"));
+
+        assert_eq!(save.get_list("skins"), vec!["classic", "night"]);
+    }
+}